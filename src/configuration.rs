@@ -1,11 +1,92 @@
 use serde_aux::field_attributes::deserialize_number_from_string;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(serde::Deserialize, Clone)]
 pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
+    pub room_cleanup: RoomCleanupSettings,
+    pub heartbeat: HeartbeatSettings,
+}
+
+/// How often rooms are swept and how long each kind of room may sit idle before it's closed
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct RoomCleanupSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub interval_seconds: u64,
+    /// Rooms waiting for players or for the game to start
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub waiting_room_timeout_seconds: u64,
+    /// Started games where nobody has moved
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub started_room_timeout_seconds: u64,
+    /// Finished games the players never left
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub finished_room_timeout_seconds: u64,
+}
+
+impl RoomCleanupSettings {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds)
+    }
+
+    /// Sweeping every 0 seconds would panic, and rooms without a timeout would close as soon
+    /// as they are swept
+    pub fn validate(&self) -> Result<(), String> {
+        let settings = [
+            ("interval_seconds", self.interval_seconds),
+            (
+                "waiting_room_timeout_seconds",
+                self.waiting_room_timeout_seconds,
+            ),
+            (
+                "started_room_timeout_seconds",
+                self.started_room_timeout_seconds,
+            ),
+            (
+                "finished_room_timeout_seconds",
+                self.finished_room_timeout_seconds,
+            ),
+        ];
+        match settings.iter().find(|(_, seconds)| *seconds == 0) {
+            Some((name, _)) => Err(format!("room_cleanup.{} must be at least 1.", name)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// How often sessions ping their client and how long they wait to hear back before hanging up
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct HeartbeatSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub interval_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub timeout_seconds: u64,
+}
+
+impl HeartbeatSettings {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds)
+    }
+
+    /// Clients only answer pings, so a timeout no longer than the interval would hang up on
+    /// every one of them
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_seconds == 0 {
+            return Err("heartbeat.interval_seconds must be at least 1.".into());
+        }
+        if self.timeout_seconds <= self.interval_seconds {
+            return Err("heartbeat.timeout_seconds must be longer than the interval.".into());
+        }
+
+        Ok(())
+    }
 }
 
 pub fn get_configuration() -> Result<ApplicationSettings, config::ConfigError> {
@@ -26,7 +107,14 @@ pub fn get_configuration() -> Result<ApplicationSettings, config::ConfigError> {
         )
         .add_source(config::Environment::with_prefix("NTTT").separator("__"));
 
-    builder.build()?.try_deserialize()
+    let settings: ApplicationSettings = builder.build()?.try_deserialize()?;
+    settings
+        .room_cleanup
+        .validate()
+        .and_then(|_| settings.heartbeat.validate())
+        .map_err(config::ConfigError::Message)?;
+
+    Ok(settings)
 }
 
 pub enum Environment {
@@ -57,3 +145,45 @@ impl TryFrom<String> for Environment {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{HeartbeatSettings, RoomCleanupSettings};
+
+    #[test]
+    pub fn zero_room_cleanup_settings_are_rejected() {
+        let settings = RoomCleanupSettings {
+            interval_seconds: 60,
+            waiting_room_timeout_seconds: 900,
+            started_room_timeout_seconds: 1800,
+            finished_room_timeout_seconds: 300,
+        };
+        assert_eq!(settings.validate(), Ok(()));
+
+        let settings = RoomCleanupSettings {
+            interval_seconds: 0,
+            ..settings
+        };
+        assert_eq!(
+            settings.validate(),
+            Err("room_cleanup.interval_seconds must be at least 1.".into())
+        );
+    }
+
+    #[test]
+    pub fn heartbeat_timeouts_must_outlast_the_interval() {
+        for (interval_seconds, timeout_seconds, valid) in
+            [(5, 10, true), (0, 10, false), (5, 5, false), (5, 0, false)]
+        {
+            let settings = HeartbeatSettings {
+                interval_seconds,
+                timeout_seconds,
+            };
+            assert_eq!(
+                settings.validate().is_ok(),
+                valid,
+                "{interval_seconds}s interval, {timeout_seconds}s timeout"
+            );
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::game_server::domain::TeamSymbol;
+
+/// Directions a line can run in, the opposite directions are covered by walking backwards
+const LINE_DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub struct Cell {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Cell {
+    pub fn new(row: usize, column: usize) -> Self {
+        Cell { row, column }
+    }
+
+    fn offset(&self, row_step: isize, column_step: isize) -> Option<Cell> {
+        Some(Cell {
+            row: self.row.checked_add_signed(row_step)?,
+            column: self.column.checked_add_signed(column_step)?,
+        })
+    }
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.row, self.column)
+    }
+}
+
+/// Result of playing a move, seen from the player who made it
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum MoveOutcome {
+    Continue,
+    Victory,
+    /// The move ended the match with a loss for the player who made it
+    Defeat,
+    Tie,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Mark {
+    pub symbol: TeamSymbol,
+    pub player_id: Uuid,
+}
+
+/// An m,n,k board: `width` columns by `height` rows where `win_length` marks in a row win
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub width: usize,
+    pub height: usize,
+    pub win_length: usize,
+    pub cells: HashMap<Cell, Mark>,
+}
+
+impl Board {
+    pub fn new(width: usize, height: usize, win_length: usize) -> Self {
+        Board {
+            width,
+            height,
+            win_length,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Whether the board is the classic 3x3 one, which keeps using the legacy move codes
+    pub fn is_classic(&self) -> bool {
+        self.width == 3 && self.height == 3
+    }
+
+    pub fn contains(&self, cell: &Cell) -> bool {
+        cell.row < self.height && cell.column < self.width
+    }
+
+    pub fn is_occupied(&self, cell: &Cell) -> bool {
+        self.cells.contains_key(cell)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.cells.len() == self.width * self.height
+    }
+
+    /// Bottom-most free row of `column`, `None` when the column is full or off the board
+    pub fn lowest_empty_row(&self, column: usize) -> Option<usize> {
+        if column >= self.width {
+            return None;
+        }
+
+        (0..self.height)
+            .rev()
+            .find(|row| !self.is_occupied(&Cell::new(*row, column)))
+    }
+
+    pub fn place(&mut self, cell: Cell, mark: Mark) {
+        self.cells.insert(cell, mark);
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Whether the mark on `cell` is part of a line of at least `win_length` equal symbols
+    pub fn completes_line(&self, cell: &Cell) -> bool {
+        let Some(mark) = self.cells.get(cell) else {
+            return false;
+        };
+
+        LINE_DIRECTIONS.iter().any(|(row_step, column_step)| {
+            let forward = self.count_in_direction(cell, mark.symbol, *row_step, *column_step);
+            let backward = self.count_in_direction(cell, mark.symbol, -row_step, -column_step);
+
+            1 + forward + backward >= self.win_length
+        })
+    }
+
+    fn count_in_direction(
+        &self,
+        cell: &Cell,
+        symbol: TeamSymbol,
+        row_step: isize,
+        column_step: isize,
+    ) -> usize {
+        let mut count = 0;
+        let mut current = cell.offset(row_step, column_step);
+
+        while let Some(next) = current.filter(|c| self.contains(c)) {
+            match self.cells.get(&next) {
+                Some(mark) if mark.symbol == symbol => count += 1,
+                _ => break,
+            }
+            current = next.offset(row_step, column_step);
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Board, Cell, Mark};
+    use crate::game_server::domain::TeamSymbol;
+    use uuid::Uuid;
+
+    fn place_all(board: &mut Board, cells: &[(usize, usize)], symbol: TeamSymbol) {
+        let player_id = Uuid::new_v4();
+        for (row, column) in cells {
+            board.place(Cell::new(*row, *column), Mark { symbol, player_id });
+        }
+    }
+
+    #[test]
+    pub fn detects_lines_of_win_length() {
+        let mut board = Board::new(15, 15, 5);
+        place_all(
+            &mut board,
+            &[(7, 3), (7, 4), (7, 5), (7, 6)],
+            TeamSymbol::Cross,
+        );
+
+        assert!(!board.completes_line(&Cell::new(7, 5)));
+
+        place_all(&mut board, &[(7, 7)], TeamSymbol::Cross);
+
+        assert!(board.completes_line(&Cell::new(7, 5)));
+    }
+
+    #[test]
+    pub fn detects_diagonal_lines() {
+        let mut board = Board::new(4, 4, 4);
+        place_all(
+            &mut board,
+            &[(0, 3), (1, 2), (2, 1), (3, 0)],
+            TeamSymbol::Circle,
+        );
+
+        assert!(board.completes_line(&Cell::new(2, 1)));
+    }
+
+    #[test]
+    pub fn lines_are_not_broken_by_board_edges() {
+        let mut board = Board::new(4, 4, 4);
+        place_all(&mut board, &[(0, 2), (0, 3)], TeamSymbol::Cross);
+        place_all(&mut board, &[(1, 0), (1, 1)], TeamSymbol::Cross);
+
+        assert!(!board.completes_line(&Cell::new(0, 3)));
+    }
+
+    #[test]
+    pub fn lines_of_other_symbols_do_not_count() {
+        let mut board = Board::new(3, 3, 3);
+        place_all(&mut board, &[(0, 0), (0, 1)], TeamSymbol::Cross);
+        place_all(&mut board, &[(0, 2)], TeamSymbol::Circle);
+
+        assert!(!board.completes_line(&Cell::new(0, 2)));
+        assert!(!board.completes_line(&Cell::new(0, 1)));
+    }
+
+    #[test]
+    pub fn marks_drop_to_the_lowest_empty_row() {
+        let mut board = Board::new(7, 6, 4);
+
+        assert_eq!(board.lowest_empty_row(3), Some(5));
+
+        place_all(&mut board, &[(5, 3), (4, 3)], TeamSymbol::Cross);
+
+        assert_eq!(board.lowest_empty_row(3), Some(3));
+        assert_eq!(board.lowest_empty_row(7), None);
+
+        place_all(
+            &mut board,
+            &[(3, 3), (2, 3), (1, 3), (0, 3)],
+            TeamSymbol::Circle,
+        );
+
+        assert_eq!(board.lowest_empty_row(3), None);
+    }
+}
@@ -1,32 +1,235 @@
-use serde::Serialize;
-
-#[derive(Debug, Serialize)]
-pub enum CommandCategory {
-    Connected,
-    PlayerConnected,
-    PlayerLeft,
-    MatchCreated,
-    MatchJoined,
-    MatchList,
-    GameStart,
-    Turn,
-    GameOver,
-}
-
-#[derive(Debug, Serialize)]
-pub struct Commmand<S: Serialize> {
-    pub category: CommandCategory,
-    pub body: S,
-}
-
-impl<S: Serialize> Commmand<S> {
-    pub fn new(category: CommandCategory, body: S) -> Self {
-        Commmand { category, body }
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::board::Cell;
+use super::domain::{MatchCursor, MatchOptions, PresenceState, TeamSymbol, Variant};
+use super::server::GameRoomStatus;
+use super::stats::StatsSummary;
+
+/// Event handed to sessions, each of them serializes it right before writing it to its socket
+/// as `{"category": <variant>, "body": <payload>}`
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(tag = "category", content = "body")]
+pub enum OutboundEvent {
+    Connected(Connection),
+    PlayerConnected(SeatedPlayer),
+    PlayerLeft(Empty),
+    MatchCreated(MatchCreated),
+    MatchJoined(MatchJoined),
+    MatchSpectated(MatchSnapshot),
+    MatchList(MatchList),
+    RoomCreated(RoomSummary),
+    RoomUpdated(RoomSummary),
+    RoomRemoved(RoomRemoved),
+    RoomClosed(RoomClosed),
+    GameStart(Empty),
+    /// Move as described by the room's rules, or in the notation the recipient picked
+    Turn(serde_json::Value),
+    Collapse(CollapseResult),
+    GameOver(GameOver),
+    GameRecorded(GameRecorded),
+    Replay(ReplayStatus),
+    Stats(StatsSummary),
+    Chat(ChatMessage),
+    LobbyChat(ChatMessage),
+    Presence(Presence),
+    Error(String),
+}
+
+/// Body of events that carry nothing, sent as an empty string
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Empty;
+
+impl Serialize for Empty {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("")
     }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection {
+    pub player_id: Uuid,
+    /// Sent back in a `Reconnect` message on a later connection to play as the same player
+    pub token: Uuid,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatedPlayer {
+    pub player_id: Uuid,
+    pub username: String,
+    pub symbol: TeamSymbol,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum MatchCreated {
+    /// Bare match id, answered to rooms created by name only
+    MatchId(Uuid),
+    Details(MatchDetails),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchDetails {
+    pub match_id: Uuid,
+    pub room_name: String,
+    pub invite_code: String,
+    pub options: MatchOptions,
+    pub password_protected: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct MatchJoined {
+    /// Everyone seated in the room, in seat order
+    pub players: Vec<SeatedPlayer>,
+}
+
+/// State of a room as a spectator first sees it
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSnapshot {
+    pub match_id: Uuid,
+    pub room_name: String,
+    pub status: GameRoomStatus,
+    /// Everyone seated in the room, in seat order
+    pub players: Vec<SeatedPlayer>,
+    pub current_turn: TeamSymbol,
+    pub variant: Variant,
+    /// Username of whoever placed each mark, by cell
+    pub board: BTreeMap<String, String>,
+    pub symbols: BTreeMap<String, TeamSymbol>,
+    pub state: serde_json::Value,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchList {
+    pub matches: Vec<RoomSummary>,
+    pub total: usize,
+    pub next_cursor: Option<MatchCursor>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomSummary {
+    pub match_id: Uuid,
+    pub room_name: String,
+    /// Seated players out of the room's capacity, as `taken/total`
+    pub players: String,
+    pub spectators: usize,
+    pub status: GameRoomStatus,
+    pub password_protected: bool,
+    pub options: MatchOptions,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomRemoved {
+    pub match_id: Uuid,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct RoomClosed {
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct CollapseResult {
+    pub cell: Cell,
+    pub board: serde_json::Value,
+    /// Final scores once the collapse ended the game, `null` otherwise
+    pub scores: serde_json::Value,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum GameOver {
+    /// How the game ended for the player it is sent to
+    Result(PlayerResult),
+    /// Username of the winner, as sent to spectators
+    Winner { winner: Option<String> },
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayerResult {
+    Victory,
+    Defeat,
+    Tie,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRecorded {
+    pub game_id: Uuid,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStatus {
+    pub game_id: Uuid,
+    pub position: usize,
+    pub total: usize,
+    pub paused: bool,
+    pub speed: f64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    /// Id to mute the sender by
+    pub sender_id: Uuid,
+    pub sender: String,
+    pub message: String,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Presence {
+    pub players: Vec<PlayerPresence>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerPresence {
+    pub player_id: Uuid,
+    pub username: String,
+    pub state: PresenceState,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Empty, GameOver, OutboundEvent, PlayerResult, RoomClosed};
 
-    pub fn new_serialized(category: CommandCategory, body: S) -> String {
-        let command = Self::new(category, body);
+    #[test]
+    pub fn events_are_sent_as_category_and_body() {
+        let cases = [
+            (
+                OutboundEvent::GameStart(Empty),
+                serde_json::json!({ "category": "GameStart", "body": "" }),
+            ),
+            (
+                OutboundEvent::GameOver(GameOver::Result(PlayerResult::Victory)),
+                serde_json::json!({ "category": "GameOver", "body": "victory" }),
+            ),
+            (
+                OutboundEvent::GameOver(GameOver::Winner { winner: None }),
+                serde_json::json!({ "category": "GameOver", "body": { "winner": null } }),
+            ),
+            (
+                OutboundEvent::RoomClosed(RoomClosed {
+                    reason: "Idle".into(),
+                }),
+                serde_json::json!({ "category": "RoomClosed", "body": { "reason": "Idle" } }),
+            ),
+        ];
 
-        serde_json::to_string(&command).unwrap_or("".into())
+        for (event, expected) in cases {
+            assert_eq!(serde_json::to_value(&event).unwrap(), expected);
+        }
     }
 }
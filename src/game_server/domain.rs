@@ -1,9 +1,12 @@
 use actix::dev::{MessageResponse, OneshotSender};
 use actix::prelude::{Actor, Message};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone)]
+use crate::game_server::board::Cell;
+use crate::game_server::GameRoomStatus;
+
+#[derive(Debug, Serialize, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum TurnMove {
     LL,
     ML,
@@ -14,7 +17,6 @@ pub enum TurnMove {
     LR,
     MR,
     UR,
-    None,
 }
 
 impl std::fmt::Display for TurnMove {
@@ -29,46 +31,191 @@ impl std::fmt::Display for TurnMove {
             Self::LR => write!(f, "LR"),
             Self::MR => write!(f, "MR"),
             Self::UR => write!(f, "UR"),
-            Self::None => write!(f, ""),
         }
     }
 }
 
-impl From<&str> for TurnMove {
-    fn from(value: &str) -> Self {
-        match value {
-            "LL" => Self::LL,
-            "ML" => Self::ML,
-            "UL" => Self::UL,
-            "LM" => Self::LM,
-            "MM" => Self::MM,
-            "UM" => Self::UM,
-            "LR" => Self::LR,
-            "MR" => Self::MR,
-            "UR" => Self::UR,
-            _ => Self::None,
+/// Reads the legacy codes, numpad digits laid out like a keypad ("7" is the upper left cell)
+/// and algebraic squares with files a-c from the left and ranks 1-3 from the bottom
+impl TryFrom<&str> for TurnMove {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let code = match value {
+            "LL" => Some(Self::LL),
+            "ML" => Some(Self::ML),
+            "UL" => Some(Self::UL),
+            "LM" => Some(Self::LM),
+            "MM" => Some(Self::MM),
+            "UM" => Some(Self::UM),
+            "LR" => Some(Self::LR),
+            "MR" => Some(Self::MR),
+            "UR" => Some(Self::UR),
+            _ => None,
+        };
+
+        code.or_else(|| Self::from_numpad(value))
+            .or_else(|| Self::from_algebraic(value))
+            .ok_or_else(|| format!("Unknown move \"{}\".", value))
+    }
+}
+
+impl TurnMove {
+    /// Cell of the move on a 3x3 board, where the first letter is the row and the second the column
+    pub fn to_cell(&self) -> Cell {
+        let (row, column) = match self {
+            Self::UL => (0, 0),
+            Self::UM => (0, 1),
+            Self::UR => (0, 2),
+            Self::ML => (1, 0),
+            Self::MM => (1, 1),
+            Self::MR => (1, 2),
+            Self::LL => (2, 0),
+            Self::LM => (2, 1),
+            Self::LR => (2, 2),
+        };
+        Cell::new(row, column)
+    }
+
+    pub fn from_cell(cell: &Cell) -> Option<Self> {
+        match (cell.row, cell.column) {
+            (0, 0) => Some(Self::UL),
+            (0, 1) => Some(Self::UM),
+            (0, 2) => Some(Self::UR),
+            (1, 0) => Some(Self::ML),
+            (1, 1) => Some(Self::MM),
+            (1, 2) => Some(Self::MR),
+            (2, 0) => Some(Self::LL),
+            (2, 1) => Some(Self::LM),
+            (2, 2) => Some(Self::LR),
+            _ => None,
+        }
+    }
+
+    fn from_numpad(value: &str) -> Option<Self> {
+        let digit = match value.as_bytes() {
+            [digit @ b'1'..=b'9'] => (digit - b'1') as usize,
+            _ => return None,
+        };
+        Self::from_cell(&Cell::new(2 - digit / 3, digit % 3))
+    }
+
+    fn from_algebraic(value: &str) -> Option<Self> {
+        let (file, rank) = match value.to_ascii_lowercase().as_bytes() {
+            [file @ b'a'..=b'c', rank @ b'1'..=b'3'] => {
+                ((file - b'a') as usize, (rank - b'1') as usize)
+            }
+            _ => return None,
+        };
+        Self::from_cell(&Cell::new(2 - rank, file))
+    }
+
+    /// The move written in the given notation
+    pub fn notation(&self, notation: MoveNotation) -> String {
+        let cell = self.to_cell();
+        match notation {
+            MoveNotation::Code => self.to_string(),
+            MoveNotation::Numpad => ((2 - cell.row) * 3 + cell.column + 1).to_string(),
+            MoveNotation::Algebraic => {
+                format!("{}{}", (b'a' + cell.column as u8) as char, 3 - cell.row)
+            }
         }
     }
 }
 
-pub struct RoomResponse(pub Option<Uuid>);
+/// How moves on the classic board are written in the `Turn` messages a session receives
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum MoveNotation {
+    #[default]
+    Code,
+    Numpad,
+    Algebraic,
+}
 
-impl<A, M> MessageResponse<A, M> for RoomResponse
-where
-    A: Actor,
-    M: Message<Result = RoomResponse>,
-{
-    fn handle(self, _: &mut A::Context, tx: Option<OneshotSender<M::Result>>) {
-        if let Some(tx) = tx {
-            let _ = tx.send(self);
+/// Move as sent by a player, either a legacy 3x3 code or board coordinates
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TurnInput {
+    Code(String),
+    Coordinates(MoveInput),
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveInput {
+    /// Sub-board the move is played on, for variants with more than one board
+    pub board: Option<usize>,
+    pub row: Option<usize>,
+    pub column: Option<usize>,
+    /// Symbol to place, chosen by the mover in wild matches
+    pub symbol: Option<TeamSymbol>,
+    /// Pair of cells a quantum move is entangled between
+    pub cells: Option<Vec<Cell>>,
+}
+
+impl TurnInput {
+    pub fn to_cell(&self) -> Result<Cell, String> {
+        match self {
+            Self::Code(code) => {
+                TurnMove::try_from(code.as_str()).map(|turn_move| turn_move.to_cell())
+            }
+            Self::Coordinates(MoveInput {
+                row: Some(row),
+                column: Some(column),
+                ..
+            }) => Ok(Cell::new(*row, *column)),
+            Self::Coordinates(_) => Err("Moves need a row and a column.".into()),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+impl std::fmt::Display for TurnInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::Code(code) => write!(f, "{}", code),
+            Self::Coordinates(input) => write!(f, "{:?}", input),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum TeamSymbol {
+    #[serde(alias = "X")]
     Cross,
+    #[serde(alias = "O")]
     Circle,
+    /// Third seat, only used in three player matches
+    #[serde(alias = "T")]
+    Triangle,
+}
+
+impl TeamSymbol {
+    /// Symbol of the seat taken by the `index`th player to enter a room
+    pub fn for_seat(index: usize) -> Self {
+        match index {
+            0 => Self::Cross,
+            1 => Self::Circle,
+            _ => Self::Triangle,
+        }
+    }
+
+    /// Single letter used for the symbol in game records
+    pub fn letter(&self) -> char {
+        match self {
+            Self::Cross => 'X',
+            Self::Circle => 'O',
+            Self::Triangle => 'T',
+        }
+    }
+
+    /// Symbol playing after this one in a room with `player_count` seats
+    pub fn next(&self, player_count: usize) -> Self {
+        match self {
+            Self::Cross => Self::Circle,
+            Self::Circle if player_count > 2 => Self::Triangle,
+            _ => Self::Cross,
+        }
+    }
 }
 
 impl std::fmt::Display for TeamSymbol {
@@ -76,6 +223,7 @@ impl std::fmt::Display for TeamSymbol {
         match &self {
             Self::Cross => write!(f, "Cross"),
             Self::Circle => write!(f, "Circle"),
+            Self::Triangle => write!(f, "Triangle"),
         }
     }
 }
@@ -92,23 +240,427 @@ where
     }
 }
 
+pub const MAX_ROOM_NAME_LENGTH: usize = 50;
+pub const MAX_PASSWORD_LENGTH: usize = 50;
+pub const MAX_SERIES_LENGTH: u8 = 9;
+pub const MIN_BOARD_SIZE: usize = 3;
+pub const MAX_BOARD_SIZE: usize = 19;
+pub const MAX_NOTAKTO_BOARDS: usize = 5;
+pub const MAX_PLAYER_COUNT: usize = 3;
+const MIN_MULTIPLAYER_BOARD_SIZE: usize = 4;
+const DEFAULT_MAX_WIN_LENGTH: usize = 5;
+
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Nine 3x3 boards in a 3x3 grid, the cell played decides the board the opponent plays next
+    Ultimate,
+    /// Completing a line loses the match
+    Misere,
+    /// Connect-four style, players pick a column and the mark falls to its lowest empty row
+    Gravity,
+    /// Players choose which symbol to place every turn, any completed line wins for the mover
+    Wild,
+    /// Both players place crosses on several boards, whoever completes the line on the last live board loses
+    Notakto,
+    /// Moves entangle two cells, cycles collapse into classical marks chosen by the other player
+    Quantum,
+}
+
+impl Variant {
+    /// Variants always played on a single 3x3 board with three in a row
+    pub fn has_fixed_board(&self) -> bool {
+        matches!(
+            self,
+            Variant::Ultimate | Variant::Notakto | Variant::Quantum
+        )
+    }
+
+    /// Whether the symbol of every mark is the one of the player who placed it, wild and
+    /// notakto players place whichever symbol they pick or share
+    pub fn marks_show_mover(&self) -> bool {
+        !matches!(self, Variant::Wild | Variant::Notakto)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum FirstMove {
+    #[default]
+    Creator,
+    Joiner,
+    Random,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeControl {
+    pub initial_seconds: u32,
+    #[serde(default)]
+    pub increment_seconds: u32,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchOptions {
+    pub variant: Variant,
+    /// Number of columns, and of rows unless `board_height` is given
+    pub board_size: usize,
+    pub board_height: Option<usize>,
+    /// Marks in a row needed to win, defaults to the board size capped at five
+    pub win_length: Option<usize>,
+    /// Number of boards played at once in notakto matches
+    pub board_count: Option<usize>,
+    /// Seats that have to be filled before the match can start
+    pub player_count: usize,
+    pub time_control: Option<TimeControl>,
+    pub rated: bool,
+    pub private: bool,
+    #[serde(skip_serializing)]
+    pub password: Option<String>,
+    pub allow_spectators: bool,
+    pub series_length: u8,
+    pub first_move: FirstMove,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        MatchOptions {
+            variant: Variant::Standard,
+            board_size: 3,
+            board_height: None,
+            win_length: None,
+            board_count: None,
+            player_count: 2,
+            time_control: None,
+            rated: false,
+            private: false,
+            password: None,
+            allow_spectators: true,
+            series_length: 1,
+            first_move: FirstMove::Creator,
+        }
+    }
+}
+
+impl MatchOptions {
+    pub fn is_password_protected(&self) -> bool {
+        self.password.is_some()
+    }
+
+    pub fn board_width(&self) -> usize {
+        self.board_size
+    }
+
+    pub fn board_height(&self) -> usize {
+        self.board_height.unwrap_or(self.board_size)
+    }
+
+    pub fn win_length(&self) -> usize {
+        self.win_length.unwrap_or_else(|| {
+            self.board_width()
+                .min(self.board_height())
+                .min(DEFAULT_MAX_WIN_LENGTH)
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for dimension in [self.board_width(), self.board_height()] {
+            if !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&dimension) {
+                return Err(format!(
+                    "Board dimensions must be between {} and {}.",
+                    MIN_BOARD_SIZE, MAX_BOARD_SIZE
+                ));
+            }
+        }
+
+        if self.variant.has_fixed_board()
+            && (self.board_size != 3 || self.board_height.is_some() || self.win_length.is_some())
+        {
+            return Err(format!(
+                "{:?} matches are always played on 3x3 boards.",
+                self.variant
+            ));
+        }
+
+        if !(2..=MAX_PLAYER_COUNT).contains(&self.player_count) {
+            return Err(format!(
+                "Player count must be between 2 and {}.",
+                MAX_PLAYER_COUNT
+            ));
+        }
+        if self.player_count > 2 {
+            if self.variant != Variant::Standard {
+                return Err("Only standard matches can have more than two players.".into());
+            }
+            if self.board_width().min(self.board_height()) < MIN_MULTIPLAYER_BOARD_SIZE {
+                return Err(format!(
+                    "Matches with more than two players need a board of at least {0}x{0}.",
+                    MIN_MULTIPLAYER_BOARD_SIZE
+                ));
+            }
+        }
+
+        if let Some(board_count) = self.board_count {
+            if self.variant != Variant::Notakto {
+                return Err("Only notakto matches can be played on several boards.".into());
+            }
+            if !(1..=MAX_NOTAKTO_BOARDS).contains(&board_count) {
+                return Err(format!(
+                    "Board count must be between 1 and {}.",
+                    MAX_NOTAKTO_BOARDS
+                ));
+            }
+        }
+
+        let longest_side = self.board_width().max(self.board_height());
+        if !(MIN_BOARD_SIZE..=longest_side).contains(&self.win_length()) {
+            return Err(format!(
+                "Win length must be between {} and {}.",
+                MIN_BOARD_SIZE, longest_side
+            ));
+        }
+
+        if let Some(time_control) = &self.time_control {
+            if !(10..=3600).contains(&time_control.initial_seconds) {
+                return Err("Initial time must be between 10 and 3600 seconds.".into());
+            }
+            if time_control.increment_seconds > 60 {
+                return Err("Time increment can't be over 60 seconds.".into());
+            }
+        }
+
+        if let Some(password) = &self.password {
+            if password.is_empty() || password.chars().count() > MAX_PASSWORD_LENGTH {
+                return Err(format!(
+                    "Password must be between 1 and {} characters.",
+                    MAX_PASSWORD_LENGTH
+                ));
+            }
+        }
+
+        if self.series_length > MAX_SERIES_LENGTH || self.series_length % 2 != 1 {
+            return Err(format!(
+                "Series length must be an odd number between 1 and {}.",
+                MAX_SERIES_LENGTH
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+pub const DEFAULT_MATCH_LIST_LIMIT: usize = 50;
+pub const MAX_MATCH_LIST_LIMIT: usize = 100;
+
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum MatchSort {
+    #[default]
+    Newest,
+    Oldest,
+    Name,
+}
+
+/// Position of the last match of a page, the next page starts right after it
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MatchCursor {
+    pub sequence: u64,
+    pub name: String,
+}
+
+impl MatchCursor {
+    pub fn compare(&self, other: &MatchCursor, sort: MatchSort) -> std::cmp::Ordering {
+        match sort {
+            MatchSort::Newest => other.sequence.cmp(&self.sequence),
+            MatchSort::Oldest => self.sequence.cmp(&other.sequence),
+            MatchSort::Name => self
+                .name
+                .to_lowercase()
+                .cmp(&other.name.to_lowercase())
+                .then(self.sequence.cmp(&other.sequence)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchListQuery {
+    pub status: Option<GameRoomStatus>,
+    pub joinable_only: bool,
+    pub variant: Option<Variant>,
+    pub search: Option<String>,
+    pub rated: Option<bool>,
+    pub sort: MatchSort,
+    pub cursor: Option<MatchCursor>,
+    pub limit: Option<usize>,
+}
+
+impl MatchListQuery {
+    pub fn page_size(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_MATCH_LIST_LIMIT)
+            .clamp(1, MAX_MATCH_LIST_LIMIT)
+    }
+}
+
+pub const MIN_REPLAY_SPEED: f64 = 0.25;
+pub const MAX_REPLAY_SPEED: f64 = 16.0;
+
+/// How a recorded game is streamed back, moves are a second apart at speed 1
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReplayOptions {
+    pub game_id: Uuid,
+    pub speed: f64,
+    /// Whether the replay waits on the first move for the client to step through it
+    pub paused: bool,
+}
+
+impl ReplayOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_replay_speed(self.speed)
+    }
+}
+
+pub fn validate_replay_speed(speed: f64) -> Result<(), String> {
+    if !(MIN_REPLAY_SPEED..=MAX_REPLAY_SPEED).contains(&speed) {
+        return Err(format!(
+            "Replay speed must be between {} and {}.",
+            MIN_REPLAY_SPEED, MAX_REPLAY_SPEED
+        ));
+    }
+
+    Ok(())
+}
+
+/// Changes to a running replay
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize)]
+pub enum ReplayControl {
+    Pause,
+    Resume,
+    /// Pauses the replay and shows the next move
+    StepForward,
+    /// Pauses the replay and takes the last move back
+    StepBack,
+    /// Moves the replay to right after the given number of moves
+    Seek(usize),
+    Speed(f64),
+    Stop,
+}
+
+/// A room can be referred to by its id or by its short invite code
+#[derive(Debug, PartialEq, Clone)]
+pub enum RoomReference {
+    Id(Uuid),
+    InviteCode(String),
+}
+
+impl From<&str> for RoomReference {
+    fn from(value: &str) -> Self {
+        match Uuid::try_parse(value) {
+            Ok(id) => Self::Id(id),
+            Err(_) => Self::InviteCode(value.trim().to_uppercase()),
+        }
+    }
+}
+
+impl std::fmt::Display for RoomReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::Id(id) => write!(f, "{}", id),
+            Self::InviteCode(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+pub enum PresenceState {
+    Idle,
+    InQueue,
+    Playing,
+    Spectating,
+}
+
 #[cfg(test)]
 mod tests {
+    use super::Cell;
+    use super::MatchCursor;
+    use super::MatchOptions;
+    use super::MatchSort;
+    use super::MoveNotation;
+    use super::RoomReference;
     use super::TeamSymbol;
     use super::TurnMove;
+    use super::Variant;
+    use uuid::Uuid;
 
     #[test]
     pub fn turn_move_deserializes_correctly() {
-        assert_eq!(Into::<TurnMove>::into("LL"), TurnMove::LL);
-        assert_eq!(Into::<TurnMove>::into("ML"), TurnMove::ML);
-        assert_eq!(Into::<TurnMove>::into("UL"), TurnMove::UL);
-        assert_eq!(Into::<TurnMove>::into("LM"), TurnMove::LM);
-        assert_eq!(Into::<TurnMove>::into("MM"), TurnMove::MM);
-        assert_eq!(Into::<TurnMove>::into("UM"), TurnMove::UM);
-        assert_eq!(Into::<TurnMove>::into("LR"), TurnMove::LR);
-        assert_eq!(Into::<TurnMove>::into("MR"), TurnMove::MR);
-        assert_eq!(Into::<TurnMove>::into("UR"), TurnMove::UR);
-        assert_eq!(Into::<TurnMove>::into("other"), TurnMove::None);
+        assert_eq!(TurnMove::try_from("LL"), Ok(TurnMove::LL));
+        assert_eq!(TurnMove::try_from("ML"), Ok(TurnMove::ML));
+        assert_eq!(TurnMove::try_from("UL"), Ok(TurnMove::UL));
+        assert_eq!(TurnMove::try_from("LM"), Ok(TurnMove::LM));
+        assert_eq!(TurnMove::try_from("MM"), Ok(TurnMove::MM));
+        assert_eq!(TurnMove::try_from("UM"), Ok(TurnMove::UM));
+        assert_eq!(TurnMove::try_from("LR"), Ok(TurnMove::LR));
+        assert_eq!(TurnMove::try_from("MR"), Ok(TurnMove::MR));
+        assert_eq!(TurnMove::try_from("UR"), Ok(TurnMove::UR));
+        assert!(TurnMove::try_from("other").is_err());
+        assert!(TurnMove::try_from("").is_err());
+    }
+
+    #[test]
+    pub fn turn_move_reads_numpad_and_algebraic_notation() {
+        assert_eq!(TurnMove::try_from("7"), Ok(TurnMove::UL));
+        assert_eq!(TurnMove::try_from("5"), Ok(TurnMove::MM));
+        assert_eq!(TurnMove::try_from("3"), Ok(TurnMove::LR));
+        assert_eq!(TurnMove::try_from("a1"), Ok(TurnMove::LL));
+        assert_eq!(TurnMove::try_from("C3"), Ok(TurnMove::UR));
+        assert_eq!(TurnMove::try_from("b2"), Ok(TurnMove::MM));
+        assert!(TurnMove::try_from("0").is_err());
+        assert!(TurnMove::try_from("d1").is_err());
+        assert!(TurnMove::try_from("a4").is_err());
+    }
+
+    #[test]
+    pub fn turn_move_writes_every_notation() {
+        for code in ["LL", "ML", "UL", "LM", "MM", "UM", "LR", "MR", "UR"] {
+            let turn_move = TurnMove::try_from(code).unwrap();
+            for notation in [
+                MoveNotation::Code,
+                MoveNotation::Numpad,
+                MoveNotation::Algebraic,
+            ] {
+                let written = turn_move.notation(notation);
+                assert_eq!(TurnMove::try_from(written.as_str()), Ok(turn_move));
+            }
+        }
+
+        assert_eq!(TurnMove::UL.notation(MoveNotation::Numpad), "7");
+        assert_eq!(TurnMove::UL.notation(MoveNotation::Algebraic), "a3");
+    }
+
+    #[test]
+    pub fn turn_move_maps_to_cells_and_back() {
+        let moves = [
+            TurnMove::LL,
+            TurnMove::ML,
+            TurnMove::UL,
+            TurnMove::LM,
+            TurnMove::MM,
+            TurnMove::UM,
+            TurnMove::LR,
+            TurnMove::MR,
+            TurnMove::UR,
+        ];
+
+        for turn_move in moves {
+            let cell = turn_move.to_cell();
+            assert_eq!(TurnMove::from_cell(&cell), Some(turn_move));
+        }
+
+        assert_eq!(TurnMove::UL.to_cell(), Cell::new(0, 0));
+        assert_eq!(TurnMove::LR.to_cell(), Cell::new(2, 2));
+        assert_eq!(TurnMove::from_cell(&Cell::new(3, 0)), None);
     }
 
     #[test]
@@ -122,12 +674,115 @@ mod tests {
         assert_eq!(format!("{}", TurnMove::LR), "LR");
         assert_eq!(format!("{}", TurnMove::MR), "MR");
         assert_eq!(format!("{}", TurnMove::UR), "UR");
-        assert_eq!(format!("{}", TurnMove::None), "");
+    }
+
+    #[test]
+    pub fn room_reference_parses_ids_and_invite_codes() {
+        let id = Uuid::new_v4();
+
+        assert_eq!(
+            Into::<RoomReference>::into(id.to_string().as_str()),
+            RoomReference::Id(id)
+        );
+        assert_eq!(
+            Into::<RoomReference>::into(" k7pq2x "),
+            RoomReference::InviteCode("K7PQ2X".into())
+        );
+    }
+
+    #[test]
+    pub fn match_options_are_validated() {
+        assert!(MatchOptions::default().validate().is_ok());
+
+        let invalid_series = MatchOptions {
+            series_length: 2,
+            ..Default::default()
+        };
+        assert!(invalid_series.validate().is_err());
+
+        let invalid_board = MatchOptions {
+            board_size: 20,
+            ..Default::default()
+        };
+        assert!(invalid_board.validate().is_err());
+
+        let unreachable_win_length = MatchOptions {
+            board_size: 4,
+            win_length: Some(5),
+            ..Default::default()
+        };
+        assert!(unreachable_win_length.validate().is_err());
+
+        let resized_ultimate = MatchOptions {
+            variant: Variant::Ultimate,
+            board_size: 4,
+            ..Default::default()
+        };
+        assert!(resized_ultimate.validate().is_err());
+
+        let standard_with_boards = MatchOptions {
+            board_count: Some(2),
+            ..Default::default()
+        };
+        assert!(standard_with_boards.validate().is_err());
+
+        let notakto = MatchOptions {
+            variant: Variant::Notakto,
+            board_count: Some(3),
+            ..Default::default()
+        };
+        assert!(notakto.validate().is_ok());
+
+        let crowded_board = MatchOptions {
+            player_count: 3,
+            ..Default::default()
+        };
+        assert!(crowded_board.validate().is_err());
+
+        let three_players = MatchOptions {
+            player_count: 3,
+            board_size: 5,
+            win_length: Some(4),
+            ..Default::default()
+        };
+        assert!(three_players.validate().is_ok());
+    }
+
+    #[test]
+    pub fn win_length_defaults_to_board_size_capped_at_five() {
+        let four_by_four = MatchOptions {
+            board_size: 4,
+            ..Default::default()
+        };
+        let gomoku = MatchOptions {
+            board_size: 15,
+            ..Default::default()
+        };
+
+        assert_eq!(four_by_four.win_length(), 4);
+        assert_eq!(gomoku.win_length(), 5);
+    }
+
+    #[test]
+    pub fn match_cursors_are_ordered_by_sort() {
+        let first = MatchCursor {
+            sequence: 1,
+            name: "beta".into(),
+        };
+        let second = MatchCursor {
+            sequence: 2,
+            name: "Alpha".into(),
+        };
+
+        assert!(first.compare(&second, MatchSort::Oldest).is_lt());
+        assert!(first.compare(&second, MatchSort::Newest).is_gt());
+        assert!(first.compare(&second, MatchSort::Name).is_gt());
     }
 
     #[test]
     pub fn team_symbol_displays_correctly() {
         assert_eq!(format!("{}", TeamSymbol::Circle), "Circle");
         assert_eq!(format!("{}", TeamSymbol::Cross), "Cross");
+        assert_eq!(format!("{}", TeamSymbol::Triangle), "Triangle");
     }
 }
@@ -0,0 +1,91 @@
+use actix::prelude::*;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+use crate::game_server::{ChatMessage, GameServer, OutboundEvent};
+
+pub const MAX_CHAT_MESSAGE_LENGTH: usize = 200;
+pub const CHAT_RATE_LIMIT_MESSAGES: usize = 5;
+pub const CHAT_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Chat {
+    pub player_id: Uuid,
+    pub username: String,
+    pub message: String,
+}
+
+impl Handler<Chat> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Chat", skip_all, fields(player_session_id=%msg.player_id, room_id))]
+    fn handle(&mut self, msg: Chat, _: &mut Context<Self>) -> Self::Result {
+        let Some(&room_id) = self.player_rooms.get(&msg.player_id) else {
+            tracing::info!("Player is not in any room.");
+            self.send_error(&msg.player_id, "You are not in a match.");
+            return;
+        };
+        tracing::Span::current().record("room_id", room_id.to_string());
+
+        let message = match validate_chat_message(self, &msg.player_id, &msg.message) {
+            Ok(message) => message,
+            Err(reason) => {
+                tracing::info!("Chat message rejected: {reason}");
+                self.send_error(&msg.player_id, reason);
+                return;
+            }
+        };
+
+        let command =
+            OutboundEvent::Chat(build_chat_message(msg.player_id, &msg.username, &message));
+        self.send_chat_message(&room_id, &command, msg.player_id);
+    }
+}
+
+/// Trims the message and checks it against the length limit and the sender's rate limit
+pub fn validate_chat_message(
+    server: &mut GameServer,
+    player_id: &Uuid,
+    message: &str,
+) -> Result<String, &'static str> {
+    let message = message.trim();
+
+    if message.is_empty() {
+        return Err("Chat message can't be empty.");
+    }
+
+    if message.chars().count() > MAX_CHAT_MESSAGE_LENGTH {
+        return Err("Chat message is too long.");
+    }
+
+    let now = Instant::now();
+    let history = server.chat_history.entry(*player_id).or_default();
+    while history
+        .front()
+        .is_some_and(|sent_at| now.duration_since(*sent_at) > CHAT_RATE_LIMIT_WINDOW)
+    {
+        history.pop_front();
+    }
+
+    if history.len() >= CHAT_RATE_LIMIT_MESSAGES {
+        return Err("You are sending messages too quickly.");
+    }
+    history.push_back(now);
+
+    Ok(message.to_owned())
+}
+
+pub fn build_chat_message(sender_id: Uuid, username: &str, message: &str) -> ChatMessage {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+
+    ChatMessage {
+        sender_id,
+        sender: username.to_string(),
+        message: message.to_string(),
+        timestamp,
+    }
+}
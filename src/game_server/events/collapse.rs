@@ -0,0 +1,89 @@
+use actix::prelude::*;
+use std::time::Instant;
+use uuid::Uuid;
+
+use crate::game_server::board::Cell;
+use crate::game_server::events::utils::{
+    find_started_room_by_room_id, is_invalid_turn, send_game_over_messages,
+};
+use crate::game_server::record::RecordedMove;
+use crate::game_server::rules::Resolution;
+use crate::game_server::{CollapseResult, GameRoomStatus, GameServer, OutboundEvent};
+
+/// Choice of the cell the move closing an entanglement cycle collapses into
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct Collapse {
+    pub player_id: Uuid,
+    pub cell: Cell,
+}
+
+impl Handler<Collapse> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(
+        name = "Collapse",
+        skip_all,
+        fields(player_id=%msg.player_id, cell=%msg.cell, room_id)
+    )]
+    fn handle(&mut self, msg: Collapse, _: &mut Self::Context) -> Self::Result {
+        let Some(&room_id) = self.player_rooms.get(&msg.player_id) else {
+            tracing::info!("Player is not in any room.");
+            return;
+        };
+        tracing::Span::current().record("room_id", room_id.to_string());
+
+        let Some(room) = find_started_room_by_room_id(self, &room_id) else {
+            tracing::info!("Player is not in any room with status started.");
+            return;
+        };
+
+        let Some(team_symbol) = room.symbol_of(&msg.player_id) else {
+            tracing::info!("Player is not part of this match.");
+            return;
+        };
+
+        // The player who didn't close the cycle picks the collapse, it is their turn by now
+        if is_invalid_turn(room.current_turn, team_symbol) {
+            tracing::info!("Player can't collapse this cycle.");
+            return;
+        }
+
+        let Some(outcome) = room.rules.resolve_pending(msg.cell) else {
+            tracing::info!("Invalid collapse.");
+            return;
+        };
+
+        room.history.push(RecordedMove::Collapse {
+            symbol: room.current_turn,
+            cell: msg.cell,
+        });
+        room.last_activity = Instant::now();
+
+        let scores = match &outcome {
+            Resolution::Won { scores, .. } => scores.clone(),
+            _ => serde_json::Value::Null,
+        };
+        let command = OutboundEvent::Collapse(CollapseResult {
+            cell: msg.cell,
+            board: room.rules.state(),
+            scores,
+        });
+        self.send_message_all(&room_id, &command);
+        self.send_message_spectators(&room_id, &command);
+
+        let winner_id = match outcome {
+            Resolution::Undecided => return,
+            Resolution::Won { winner, .. } => Some(winner.player_id),
+            Resolution::Tied => None,
+        };
+
+        tracing::info!("Game ended after collapse");
+        if let Some(room) = self.rooms.get_mut(&room_id) {
+            room.status = GameRoomStatus::Finished;
+        }
+        send_game_over_messages(self, &room_id, winner_id);
+        self.broadcast_presence();
+        self.notify_room_updated(&room_id);
+    }
+}
@@ -2,29 +2,47 @@ use actix::prelude::*;
 use std::sync::atomic::Ordering;
 use uuid::Uuid;
 
-use crate::game_server::{CommandCategory, Commmand, GameServer, ServerMessage};
+use crate::game_server::{Connection, GameServer, OutboundEvent, ServerMessage};
 
+/// Registers a session, resolves to the player id it plays as
 #[derive(Message)]
-#[rtype(result = "()")]
+#[rtype(result = "Uuid")]
 pub struct Connect {
     pub id: Uuid,
+    /// Reconnect token handed out on an earlier connection, lets the player keep their id and stats
+    pub token: Option<Uuid>,
+    pub username: String,
     pub addr: Recipient<ServerMessage>,
 }
 
 impl Handler<Connect> for GameServer {
-    type Result = ();
+    type Result = MessageResult<Connect>;
 
     #[tracing::instrument(name = "Player connect", skip_all, fields(player_session_id=%msg.id))]
     fn handle(&mut self, msg: Connect, _: &mut Context<Self>) -> Self::Result {
-        let id = msg.id;
+        let (id, token) = match msg.token {
+            Some(token) => match self.player_tokens.get(&token) {
+                Some(player_id) if !self.sessions.contains_key(player_id) => (*player_id, token),
+                _ => (msg.id, Uuid::new_v4()),
+            },
+            None => (msg.id, Uuid::new_v4()),
+        };
+        self.remember_player(token, id);
 
         let _ = self.visitor_count.fetch_add(1, Ordering::SeqCst);
         let count = self.visitor_count.load(Ordering::Relaxed);
         tracing::info!("Number of players connected: {count}");
 
-        let connect_command = Commmand::new_serialized(CommandCategory::Connected, "");
+        let connect_command = OutboundEvent::Connected(Connection {
+            player_id: id,
+            token,
+        });
         self.send_direct_message(&msg.addr, &connect_command);
 
         self.sessions.insert(id, msg.addr);
+        self.usernames.insert(id, msg.username);
+        self.broadcast_presence();
+
+        MessageResult(id)
     }
 }
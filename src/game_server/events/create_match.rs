@@ -1,37 +1,102 @@
 use actix::prelude::*;
+use rand::Rng;
 use uuid::Uuid;
 
-use crate::game_server::{domain::RoomResponse, CommandCategory, Commmand, GameRoom, GameServer};
+use crate::game_server::{
+    domain::{MatchOptions, MAX_ROOM_NAME_LENGTH},
+    events::utils::leave_current_room,
+    GameRoom, GameServer, MatchCreated, MatchDetails, OutboundEvent,
+};
+
+const INVITE_CODE_LENGTH: usize = 6;
+// Leaves out characters that are easily confused with each other, such as 0/O and 1/I
+const INVITE_CODE_CHARSET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
 
 #[derive(Message)]
-#[rtype(result = "RoomResponse")]
+#[rtype(result = "()")]
 pub struct CreateMatch {
     pub id: Uuid,
     pub username: String,
     pub room_name: String,
+    pub options: MatchOptions,
+    /// Legacy clients created matches with a plain name and expect only the id back
+    pub legacy_response: bool,
 }
 
 impl Handler<CreateMatch> for GameServer {
-    type Result = RoomResponse;
+    type Result = ();
 
     #[tracing::instrument(name = "Create match", skip_all, fields(player_session_id=%msg.id))]
     fn handle(&mut self, msg: CreateMatch, _: &mut Context<Self>) -> Self::Result {
+        if let Err(reason) = validate_room_name(&msg.room_name).and(msg.options.validate()) {
+            tracing::info!("Invalid match options: {reason}");
+            self.send_error(&msg.id, &reason);
+            return;
+        }
+
+        if leave_current_room(self, &msg.id).is_some() {
+            tracing::info!("Player left their previous room to create a new one.");
+        }
+
         let room_id = Uuid::new_v4();
+        let invite_code = generate_invite_code(self);
+        self.rooms_created += 1;
+
+        self.rooms.insert(
+            room_id,
+            GameRoom::new(
+                msg.room_name.clone(),
+                msg.options,
+                invite_code,
+                self.rooms_created,
+            ),
+        );
 
-        self.rooms
-            .insert(room_id, GameRoom::new(msg.room_name.clone()));
+        let room = self.rooms.get_mut(&room_id).unwrap();
+        room.add_player(msg.id, msg.username);
+        self.player_rooms.insert(msg.id, room_id);
 
-        self.rooms
-            .get_mut(&room_id)
-            .unwrap()
-            .players
-            .insert(msg.id.clone(), msg.username);
+        let body = if msg.legacy_response {
+            MatchCreated::MatchId(room_id)
+        } else {
+            MatchCreated::Details(MatchDetails {
+                match_id: room_id,
+                room_name: room.name.clone(),
+                invite_code: room.invite_code.clone(),
+                options: room.options.clone(),
+                password_protected: room.options.is_password_protected(),
+            })
+        };
 
         if let Some(addr) = self.sessions.get(&msg.id) {
-            let command = Commmand::new_serialized(CommandCategory::MatchCreated, room_id);
+            let command = OutboundEvent::MatchCreated(body);
             self.send_direct_message(addr, &command);
-            return RoomResponse(Some(room_id.clone()));
+            self.broadcast_presence();
+            self.notify_room_created(&room_id);
+        }
+    }
+}
+
+fn validate_room_name(name: &str) -> Result<(), String> {
+    if name.chars().count() > MAX_ROOM_NAME_LENGTH {
+        return Err(format!(
+            "Room name can't be longer than {} characters.",
+            MAX_ROOM_NAME_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+fn generate_invite_code(server: &GameServer) -> String {
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let code: String = (0..INVITE_CODE_LENGTH)
+            .map(|_| INVITE_CODE_CHARSET[rng.gen_range(0..INVITE_CODE_CHARSET.len())] as char)
+            .collect();
+
+        if server.rooms.values().all(|room| room.invite_code != code) {
+            return code;
         }
-        return RoomResponse(None);
     }
 }
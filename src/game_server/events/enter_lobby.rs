@@ -0,0 +1,25 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::GameServer;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct EnterLobby {
+    pub player_id: Uuid,
+}
+
+impl Handler<EnterLobby> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Enter lobby", skip_all, fields(player_session_id=%msg.player_id))]
+    fn handle(&mut self, msg: EnterLobby, _: &mut Context<Self>) -> Self::Result {
+        if !self.sessions.contains_key(&msg.player_id) {
+            tracing::info!("User not found in server sessions");
+            return;
+        }
+
+        self.lobby_members.insert(msg.player_id);
+        self.broadcast_presence();
+    }
+}
@@ -0,0 +1,40 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::record::GameRecord;
+use crate::game_server::GameServer;
+
+/// Text record of a finished game, `None` when no game has this id
+#[derive(Message, Debug)]
+#[rtype(result = "Option<String>")]
+pub struct GetGameRecord(pub Uuid);
+
+impl Handler<GetGameRecord> for GameServer {
+    type Result = Option<String>;
+
+    #[tracing::instrument(name = "Get game record", skip_all, fields(game_id=%msg.0))]
+    fn handle(&mut self, msg: GetGameRecord, _: &mut Self::Context) -> Self::Result {
+        self.game_records
+            .get(&msg.0)
+            .map(|record| record.to_string())
+    }
+}
+
+/// Stores a game record written elsewhere so it can be downloaded and replayed like the
+/// games played here, failing when it can't be read, one of its moves is illegal or they
+/// don't end the game the way its `Result` header says
+#[derive(Message, Debug)]
+#[rtype(result = "Result<Uuid, String>")]
+pub struct ImportGameRecord(pub String);
+
+impl Handler<ImportGameRecord> for GameServer {
+    type Result = Result<Uuid, String>;
+
+    #[tracing::instrument(name = "Import game record", skip_all)]
+    fn handle(&mut self, msg: ImportGameRecord, _: &mut Self::Context) -> Self::Result {
+        let record: GameRecord = msg.0.parse()?;
+        record.verify()?;
+
+        Ok(self.store_imported_record(record))
+    }
+}
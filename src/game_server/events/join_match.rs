@@ -2,54 +2,73 @@ use actix::prelude::*;
 use uuid::Uuid;
 
 use crate::game_server::{
-    domain::RoomResponse, CommandCategory, Commmand, GameRoom, GameRoomStatus, GameServer,
+    domain::RoomReference, events::utils::leave_current_room, GameRoomStatus, GameServer,
+    MatchJoined, OutboundEvent,
 };
 
 #[derive(Message)]
-#[rtype(result = "RoomResponse")]
+#[rtype(result = "()")]
 pub struct JoinMatch {
     pub player_id: Uuid,
-    pub room_id: Uuid,
+    pub room: RoomReference,
+    pub password: Option<String>,
     pub username: String,
 }
 
 impl Handler<JoinMatch> for GameServer {
-    type Result = RoomResponse;
+    type Result = ();
 
-    #[tracing::instrument(name = "Join match", skip_all, fields(player_session_id=%msg.player_id))]
+    #[tracing::instrument(name = "Join match", skip_all, fields(player_session_id=%msg.player_id, room=%msg.room))]
     fn handle(&mut self, msg: JoinMatch, _: &mut Context<Self>) -> Self::Result {
-        let mut result_room: Option<(Uuid, String)> = None;
-        let mut other_player_username = String::from("");
-        if let Some((room_id, game_room)) = find_waiting_game_room(self, &msg.room_id) {
-            other_player_username = game_room.players.iter().next().unwrap().1.clone();
-            game_room
-                .players
-                .insert(msg.player_id, msg.username.clone());
-            result_room = Some((room_id.clone(), game_room.name.clone()));
+        let Some(room_id) = self.find_room_id(&msg.room) else {
+            tracing::info!("Room not found.");
+            self.send_error(&msg.player_id, "Match not found.");
+            return;
+        };
+
+        if self.player_rooms.get(&msg.player_id) == Some(&room_id) {
+            tracing::info!("Player is already in this room.");
+            self.send_error(&msg.player_id, "You are already in this match.");
+            return;
+        }
+
+        let game_room = self.rooms.get(&room_id).unwrap();
+
+        if game_room.status != GameRoomStatus::Waiting
+            || game_room.players.is_empty()
+            || game_room.is_full()
+        {
+            tracing::info!("Room is not waiting for players.");
+            self.send_error(&msg.player_id, "Match is not accepting players.");
+            return;
+        }
+
+        if !game_room.check_password(msg.password.as_deref()) {
+            tracing::info!("Incorrect room password.");
+            self.send_error(&msg.player_id, "Incorrect password.");
+            return;
+        }
+
+        if leave_current_room(self, &msg.player_id).is_some() {
+            tracing::info!("Player left their previous room to join this one.");
         }
 
-        if let Some((room_id, _)) = result_room {
-            if let Some(addr) = self.sessions.get(&msg.player_id) {
-                let command =
-                    Commmand::new_serialized(CommandCategory::MatchJoined, other_player_username);
-                self.send_direct_message(addr, &command);
+        let game_room = self.rooms.get_mut(&room_id).unwrap();
+        game_room.add_player(msg.player_id, msg.username.clone());
+        let players = game_room.seated_players();
+        self.player_rooms.insert(msg.player_id, room_id);
 
-                let command =
-                    Commmand::new_serialized(CommandCategory::PlayerConnected, &msg.username);
-                self.send_message(&room_id, &command, msg.player_id.clone());
+        if let Some(addr) = self.sessions.get(&msg.player_id) {
+            let seat = players.last().cloned();
+            let command = OutboundEvent::MatchJoined(MatchJoined { players });
+            self.send_direct_message(addr, &command);
 
-                return RoomResponse(Some(room_id.clone()));
+            if let Some(seat) = seat {
+                let command = OutboundEvent::PlayerConnected(seat);
+                self.send_message(&room_id, &command, msg.player_id);
             }
+            self.broadcast_presence();
+            self.notify_room_updated(&room_id);
         }
-        return RoomResponse(None);
     }
 }
-
-fn find_waiting_game_room<'a, 'b>(
-    server: &'a mut GameServer,
-    room_id: &'b Uuid,
-) -> Option<(&'a Uuid, &'a mut GameRoom)> {
-    server.rooms.iter_mut().find(|(id, room)| {
-        room.status == GameRoomStatus::Waiting && room.players.iter().count() == 1 && *id == room_id
-    })
-}
@@ -0,0 +1,21 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::GameServer;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct LeaveLobby {
+    pub player_id: Uuid,
+}
+
+impl Handler<LeaveLobby> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Leave lobby", skip_all, fields(player_session_id=%msg.player_id))]
+    fn handle(&mut self, msg: LeaveLobby, _: &mut Context<Self>) -> Self::Result {
+        if !self.lobby_members.remove(&msg.player_id) {
+            tracing::info!("Player is not in the lobby.");
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use actix::prelude::*;
 use uuid::Uuid;
 
-use crate::game_server::{CommandCategory, Commmand, GameServer};
+use crate::game_server::{domain::MatchListQuery, GameRoom, GameServer, MatchList, OutboundEvent};
 
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct ListMatches {
     pub player_id: Uuid,
+    pub query: MatchListQuery,
 }
 
 impl Handler<ListMatches> for GameServer {
@@ -15,24 +16,7 @@ impl Handler<ListMatches> for GameServer {
     #[tracing::instrument(name = "List matches", skip_all, fields(player_session_id=%msg.player_id))]
     fn handle(&mut self, msg: ListMatches, _: &mut Context<Self>) -> Self::Result {
         if let Some(addr) = self.sessions.get(&msg.player_id) {
-            let mut results: Vec<serde_json::Value> = vec![];
-
-            for (room_id, room) in self.rooms.iter() {
-                let player_count = format!("{}/2", room.players.len());
-                let value = serde_json::json!({
-                    "matchId": room_id.to_owned(),
-                    "roomName": room.name.to_owned(),
-                    "players": player_count,
-                    "status": room.status.to_owned(),
-                });
-
-                results.push(value);
-            }
-
-            let command = Commmand::new_serialized(
-                CommandCategory::MatchList,
-                serde_json::json!({ "matches": results }),
-            );
+            let command = OutboundEvent::MatchList(match_list(self, &msg.query));
 
             self.send_direct_message(addr, &command);
         } else {
@@ -40,3 +24,54 @@ impl Handler<ListMatches> for GameServer {
         }
     }
 }
+
+pub fn match_list(server: &GameServer, query: &MatchListQuery) -> MatchList {
+    list_rooms(server, query, query.page_size())
+}
+
+/// Every listed room in a single page, as lobby subscribers first see them
+pub fn full_match_list(server: &GameServer) -> MatchList {
+    list_rooms(
+        server,
+        &MatchListQuery::default(),
+        server.rooms.len().max(1),
+    )
+}
+
+fn list_rooms(server: &GameServer, query: &MatchListQuery, page_size: usize) -> MatchList {
+    let mut rooms: Vec<(&Uuid, &GameRoom)> = server
+        .rooms
+        .iter()
+        .filter(|(_, room)| room.is_public() && room.matches_query(query))
+        .collect();
+    let total = rooms.len();
+
+    rooms.sort_by(|(_, a), (_, b)| a.cursor().compare(&b.cursor(), query.sort));
+
+    let page: Vec<(&Uuid, &GameRoom)> = rooms
+        .into_iter()
+        .filter(|(_, room)| match &query.cursor {
+            Some(cursor) => room.cursor().compare(cursor, query.sort).is_gt(),
+            None => true,
+        })
+        .take(page_size + 1)
+        .collect();
+
+    let next_cursor = if page.len() > page_size {
+        page.get(page_size - 1).map(|(_, room)| room.cursor())
+    } else {
+        None
+    };
+
+    let matches = page
+        .iter()
+        .take(page_size)
+        .map(|(room_id, room)| room.summary(room_id))
+        .collect();
+
+    MatchList {
+        matches,
+        total,
+        next_cursor,
+    }
+}
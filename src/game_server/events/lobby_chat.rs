@@ -0,0 +1,46 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::{
+    events::{build_chat_message, validate_chat_message},
+    GameServer, OutboundEvent,
+};
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct LobbyChat {
+    pub player_id: Uuid,
+    pub message: String,
+}
+
+impl Handler<LobbyChat> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Lobby chat", skip_all, fields(player_session_id=%msg.player_id))]
+    fn handle(&mut self, msg: LobbyChat, _: &mut Context<Self>) -> Self::Result {
+        if !self.is_in_lobby(&msg.player_id) {
+            tracing::info!("Player is not in the lobby.");
+            self.send_error(&msg.player_id, "You are not in the lobby.");
+            return;
+        }
+
+        let message = match validate_chat_message(self, &msg.player_id, &msg.message) {
+            Ok(message) => message,
+            Err(reason) => {
+                tracing::info!("Lobby chat message rejected: {reason}");
+                self.send_error(&msg.player_id, reason);
+                return;
+            }
+        };
+
+        let username = self
+            .usernames
+            .get(&msg.player_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let command =
+            OutboundEvent::LobbyChat(build_chat_message(msg.player_id, &username, &message));
+        self.send_lobby_message(&command, msg.player_id);
+    }
+}
@@ -0,0 +1,24 @@
+use actix::prelude::*;
+use std::sync::atomic::Ordering;
+
+use crate::game_server::GameServer;
+
+/// Counters describing the server's current load, served over HTTP
+#[derive(Message, Debug)]
+#[rtype(result = "serde_json::Value")]
+pub struct GetMetrics;
+
+impl Handler<GetMetrics> for GameServer {
+    type Result = MessageResult<GetMetrics>;
+
+    #[tracing::instrument(name = "Get metrics", skip_all)]
+    fn handle(&mut self, _: GetMetrics, _: &mut Self::Context) -> Self::Result {
+        MessageResult(serde_json::json!({
+            "connectedPlayers": self.sessions.len(),
+            "visitors": self.visitor_count.load(Ordering::Relaxed),
+            "openRooms": self.rooms.len(),
+            "roomsCreated": self.rooms_created,
+            "roomsReaped": self.rooms_reaped,
+        }))
+    }
+}
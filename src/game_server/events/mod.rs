@@ -1,20 +1,54 @@
+mod chat;
+mod collapse;
 mod connect;
 mod create_match;
 mod disconnect;
+mod enter_lobby;
+mod game_record;
 mod game_state;
 mod join_match;
+mod leave_lobby;
 mod leave_match;
 mod list_matches;
+mod lobby_chat;
+mod metrics;
+mod mute_opponent;
+mod reconnect;
+mod replay;
+mod room_cleanup;
+mod set_notation;
+mod set_username;
+mod spectate;
 mod start_game;
+mod stats;
+mod subscribe_lobby;
 mod turn;
+mod unsubscribe_lobby;
 pub mod utils;
 
+pub use chat::*;
+pub use collapse::*;
 pub use connect::*;
 pub use create_match::*;
 pub use disconnect::*;
+pub use enter_lobby::*;
+pub use game_record::*;
 pub use game_state::*;
 pub use join_match::*;
+pub use leave_lobby::*;
 pub use leave_match::*;
 pub use list_matches::*;
+pub use lobby_chat::*;
+pub use metrics::*;
+pub use mute_opponent::*;
+pub use reconnect::*;
+pub use replay::*;
+pub use room_cleanup::*;
+pub use set_notation::*;
+pub use set_username::*;
+pub use spectate::*;
 pub use start_game::*;
+pub use stats::*;
+pub use subscribe_lobby::*;
 pub use turn::*;
+pub use unsubscribe_lobby::*;
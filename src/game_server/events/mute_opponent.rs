@@ -0,0 +1,66 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::GameServer;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct MuteOpponent {
+    pub player_id: Uuid,
+    /// Id of the player or spectator to mute, may be left out when the room has a single
+    /// opponent
+    pub target: Option<Uuid>,
+    pub mute: bool,
+}
+
+impl Handler<MuteOpponent> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Mute opponent", skip_all, fields(player_session_id=%msg.player_id, mute=%msg.mute))]
+    fn handle(&mut self, msg: MuteOpponent, _: &mut Context<Self>) -> Self::Result {
+        let room = self
+            .player_rooms
+            .get(&msg.player_id)
+            .and_then(|room_id| self.rooms.get(room_id));
+        let opponent_ids: Vec<Uuid> = match (room, msg.target) {
+            (None, _) => vec![],
+            (Some(room), None) => room
+                .players
+                .keys()
+                .filter(|id| **id != msg.player_id)
+                .copied()
+                .collect(),
+            (Some(room), Some(target)) => {
+                let in_room =
+                    room.players.contains_key(&target) || room.spectators.contains_key(&target);
+                if in_room && target != msg.player_id {
+                    vec![target]
+                } else {
+                    vec![]
+                }
+            }
+        };
+
+        let error = match (&msg.target, opponent_ids.len()) {
+            (None, 0) => Some("You have no opponent to mute."),
+            (None, 1) => None,
+            (None, _) => Some("Name the player to mute."),
+            (Some(_), 0) => Some("Player not found in this match."),
+            (Some(_), _) => None,
+        };
+        if let Some(error) = error {
+            tracing::info!("Player has no opponent to mute: {error}");
+            self.send_error(&msg.player_id, error);
+            return;
+        }
+
+        let muted = self.muted_players.entry(msg.player_id).or_default();
+        for opponent_id in opponent_ids {
+            if msg.mute {
+                muted.insert(opponent_id);
+            } else {
+                muted.remove(&opponent_id);
+            }
+        }
+    }
+}
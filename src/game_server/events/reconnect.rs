@@ -0,0 +1,49 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::events::{Connect, Disconnect};
+use crate::game_server::{GameServer, ServerMessage};
+
+/// Moves a session over to the player a reconnect token stands for, resolves to the player id
+/// the session plays as afterwards
+#[derive(Message)]
+#[rtype(result = "Uuid")]
+pub struct Reconnect {
+    pub player_id: Uuid,
+    pub token: Uuid,
+    pub username: String,
+    pub addr: Recipient<ServerMessage>,
+}
+
+impl Handler<Reconnect> for GameServer {
+    type Result = MessageResult<Reconnect>;
+
+    #[tracing::instrument(name = "Player reconnect", skip_all, fields(player_session_id=%msg.player_id))]
+    fn handle(&mut self, msg: Reconnect, ctx: &mut Context<Self>) -> Self::Result {
+        let known_player = self.player_tokens.get(&msg.token).copied();
+        let Some(player_id) = known_player.filter(|id| !self.sessions.contains_key(id)) else {
+            tracing::info!("Reconnect token is unknown or its player is still connected.");
+            self.send_error(&msg.player_id, "Unknown or already used reconnect token.");
+            return MessageResult(msg.player_id);
+        };
+        tracing::info!(%player_id, "Resuming player.");
+
+        self.handle(
+            Disconnect {
+                player_id: msg.player_id,
+            },
+            ctx,
+        );
+        let MessageResult(player_id) = self.handle(
+            Connect {
+                id: msg.player_id,
+                token: Some(msg.token),
+                username: msg.username,
+                addr: msg.addr,
+            },
+            ctx,
+        );
+
+        MessageResult(player_id)
+    }
+}
@@ -0,0 +1,232 @@
+use actix::prelude::*;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::game_server::domain::{validate_replay_speed, ReplayControl, ReplayOptions};
+use crate::game_server::{
+    Empty, GameRoomStatus, GameServer, OutboundEvent, Replay, ReplayStatus, ServerMessage,
+};
+
+/// Time between two moves of a replay played at speed 1
+const REPLAY_MOVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Streams a recorded game to the player, replacing any replay they were already watching,
+/// players seated in a started game can't watch one until it ends
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct StartReplay {
+    pub player_id: Uuid,
+    pub options: ReplayOptions,
+}
+
+impl Handler<StartReplay> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(
+        name = "Start replay",
+        skip_all,
+        fields(player_id=%msg.player_id, game_id=%msg.options.game_id)
+    )]
+    fn handle(&mut self, msg: StartReplay, ctx: &mut Self::Context) -> Self::Result {
+        if let Err(reason) = msg.options.validate() {
+            self.send_error(&msg.player_id, &reason);
+            return;
+        }
+
+        let is_playing = self
+            .player_rooms
+            .get(&msg.player_id)
+            .and_then(|room_id| self.rooms.get(room_id))
+            .is_some_and(|room| {
+                room.status == GameRoomStatus::Started && room.players.contains_key(&msg.player_id)
+            });
+        if is_playing {
+            tracing::info!("Player is in a started game.");
+            self.send_error(&msg.player_id, "You can't watch a replay during a game.");
+            return;
+        }
+
+        let Some(record) = self.game_records.get(&msg.options.game_id) else {
+            tracing::info!("Game record not found.");
+            self.send_error(&msg.player_id, "Game not found.");
+            return;
+        };
+
+        let notation = self
+            .move_notations
+            .get(&msg.player_id)
+            .copied()
+            .unwrap_or_default();
+        let steps = match record.replay(notation) {
+            Ok(steps) => steps,
+            Err(reason) => {
+                tracing::error!("Stored game record can't be replayed: {}", reason);
+                self.send_error(&msg.player_id, &reason);
+                return;
+            }
+        };
+
+        stop_replay(self, &msg.player_id, ctx);
+        self.replays.insert(
+            msg.player_id,
+            Replay {
+                game_id: msg.options.game_id,
+                steps,
+                position: 0,
+                speed: msg.options.speed,
+                paused: msg.options.paused,
+                timer: None,
+            },
+        );
+
+        seek_replay(self, &msg.player_id, 0);
+        send_replay_status(self, &msg.player_id);
+        schedule_replay_step(self, msg.player_id, ctx);
+    }
+}
+
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct ControlReplay {
+    pub player_id: Uuid,
+    pub control: ReplayControl,
+}
+
+impl Handler<ControlReplay> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(
+        name = "Control replay",
+        skip_all,
+        fields(player_id=%msg.player_id, control=?msg.control)
+    )]
+    fn handle(&mut self, msg: ControlReplay, ctx: &mut Self::Context) -> Self::Result {
+        let Some(replay) = self.replays.get_mut(&msg.player_id) else {
+            tracing::info!("Player isn't watching a replay.");
+            self.send_error(&msg.player_id, "No replay is running.");
+            return;
+        };
+
+        match msg.control {
+            ReplayControl::Pause => replay.paused = true,
+            ReplayControl::Resume => replay.paused = false,
+            ReplayControl::StepForward => {
+                replay.paused = true;
+                send_replay_step(self, &msg.player_id);
+            }
+            ReplayControl::StepBack => {
+                replay.paused = true;
+                let position = replay.position.saturating_sub(1);
+                seek_replay(self, &msg.player_id, position);
+            }
+            ReplayControl::Seek(position) => seek_replay(self, &msg.player_id, position),
+            ReplayControl::Speed(speed) => {
+                if let Err(reason) = validate_replay_speed(speed) {
+                    self.send_error(&msg.player_id, &reason);
+                    return;
+                }
+                replay.speed = speed;
+            }
+            ReplayControl::Stop => {
+                stop_replay(self, &msg.player_id, ctx);
+                return;
+            }
+        }
+
+        send_replay_status(self, &msg.player_id);
+        schedule_replay_step(self, msg.player_id, ctx);
+    }
+}
+
+/// Drops the player's replay along with the timer playing it
+pub fn stop_replay(server: &mut GameServer, player_id: &Uuid, ctx: &mut Context<GameServer>) {
+    if let Some(timer) = server
+        .replays
+        .remove(player_id)
+        .and_then(|replay| replay.timer)
+    {
+        ctx.cancel_future(timer);
+    }
+}
+
+/// Sends the next move of the replay, returning whether there was one left
+fn send_replay_step(server: &mut GameServer, player_id: &Uuid) -> bool {
+    let (Some(replay), Some(addr)) = (
+        server.replays.get_mut(player_id),
+        server.sessions.get(player_id),
+    ) else {
+        return false;
+    };
+    let Some(step) = replay.steps.get(replay.position) else {
+        return false;
+    };
+
+    addr.do_send(ServerMessage(step.clone()));
+    replay.position += 1;
+    true
+}
+
+/// Clears the client's board with a `GameStart` and sends the first `position` moves again,
+/// which is how a replay goes back or jumps ahead
+fn seek_replay(server: &mut GameServer, player_id: &Uuid, position: usize) {
+    let (Some(replay), Some(addr)) = (
+        server.replays.get_mut(player_id),
+        server.sessions.get(player_id),
+    ) else {
+        return;
+    };
+
+    replay.position = position.min(replay.steps.len());
+    let command = OutboundEvent::GameStart(Empty);
+    addr.do_send(ServerMessage(command));
+    for step in &replay.steps[..replay.position] {
+        addr.do_send(ServerMessage(step.clone()));
+    }
+}
+
+fn send_replay_status(server: &GameServer, player_id: &Uuid) {
+    let (Some(replay), Some(addr)) = (
+        server.replays.get(player_id),
+        server.sessions.get(player_id),
+    ) else {
+        return;
+    };
+
+    let command = OutboundEvent::Replay(ReplayStatus {
+        game_id: replay.game_id,
+        position: replay.position,
+        total: replay.steps.len(),
+        paused: replay.paused,
+        speed: replay.speed,
+    });
+    server.send_direct_message(addr, &command);
+}
+
+/// Plans the next move of a playing replay, replacing the one already planned
+fn schedule_replay_step(server: &mut GameServer, player_id: Uuid, ctx: &mut Context<GameServer>) {
+    let Some(replay) = server.replays.get_mut(&player_id) else {
+        return;
+    };
+    if let Some(timer) = replay.timer.take() {
+        ctx.cancel_future(timer);
+    }
+    if replay.paused || replay.position >= replay.steps.len() {
+        return;
+    }
+
+    let delay = REPLAY_MOVE_INTERVAL.div_f64(replay.speed);
+    replay.timer = Some(ctx.run_later(delay, move |server, ctx| {
+        if let Some(replay) = server.replays.get_mut(&player_id) {
+            replay.timer = None;
+        }
+        send_replay_step(server, &player_id);
+        if server
+            .replays
+            .get(&player_id)
+            .is_some_and(|replay| replay.position >= replay.steps.len())
+        {
+            send_replay_status(server, &player_id);
+        }
+        schedule_replay_step(server, player_id, ctx);
+    }));
+}
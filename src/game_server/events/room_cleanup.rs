@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use crate::game_server::{GameServer, OutboundEvent, RoomClosed};
+
+/// Closes every room that has been idle longer than allowed, telling the players and
+/// spectators in it why
+pub fn close_idle_rooms(server: &mut GameServer) {
+    let idle_rooms: Vec<(Uuid, &'static str)> = server
+        .rooms
+        .iter()
+        .filter_map(|(room_id, room)| {
+            room.idle_reason(&server.room_cleanup)
+                .map(|reason| (*room_id, reason))
+        })
+        .collect();
+
+    if idle_rooms.is_empty() {
+        return;
+    }
+
+    for (room_id, reason) in idle_rooms.iter() {
+        let command = OutboundEvent::RoomClosed(RoomClosed {
+            reason: reason.to_string(),
+        });
+        server.send_message_all(room_id, &command);
+        server.send_message_spectators(room_id, &command);
+
+        server.remove_room(room_id);
+    }
+
+    server.rooms_reaped += idle_rooms.len() as u64;
+    tracing::info!(rooms_reaped = idle_rooms.len(), "Closed idle rooms.");
+    server.broadcast_presence();
+}
@@ -0,0 +1,26 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::domain::MoveNotation;
+use crate::game_server::GameServer;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetNotation {
+    pub player_id: Uuid,
+    pub notation: MoveNotation,
+}
+
+impl Handler<SetNotation> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Set notation", skip_all, fields(player_session_id=%msg.player_id, notation=?msg.notation))]
+    fn handle(&mut self, msg: SetNotation, _: &mut Context<Self>) -> Self::Result {
+        if !self.sessions.contains_key(&msg.player_id) {
+            tracing::info!("User not found in server sessions");
+            return;
+        }
+
+        self.move_notations.insert(msg.player_id, msg.notation);
+    }
+}
@@ -0,0 +1,25 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::GameServer;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetUsername {
+    pub player_id: Uuid,
+    pub username: String,
+}
+
+impl Handler<SetUsername> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Set username", skip_all, fields(player_session_id=%msg.player_id, username=%msg.username))]
+    fn handle(&mut self, msg: SetUsername, _: &mut Context<Self>) -> Self::Result {
+        if let Some(username) = self.usernames.get_mut(&msg.player_id) {
+            *username = msg.username;
+            self.broadcast_presence();
+        } else {
+            tracing::info!("User not found in server sessions");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::{
+    domain::RoomReference, events::utils::leave_current_room, GameServer, MatchSnapshot,
+    OutboundEvent,
+};
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Spectate {
+    pub player_id: Uuid,
+    pub room: RoomReference,
+    pub password: Option<String>,
+    pub username: String,
+}
+
+impl Handler<Spectate> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Spectate match", skip_all, fields(player_session_id=%msg.player_id, room=%msg.room))]
+    fn handle(&mut self, msg: Spectate, _: &mut Context<Self>) -> Self::Result {
+        let Some(room_id) = self.find_room_id(&msg.room) else {
+            tracing::info!("Room not found.");
+            self.send_error(&msg.player_id, "Match not found.");
+            return;
+        };
+
+        if self.player_rooms.get(&msg.player_id) == Some(&room_id) {
+            tracing::info!("Player is already in this room.");
+            self.send_error(&msg.player_id, "You are already in this match.");
+            return;
+        }
+
+        let room = self.rooms.get(&room_id).unwrap();
+
+        if !room.options.allow_spectators {
+            tracing::info!("Room does not allow spectators.");
+            self.send_error(&msg.player_id, "Spectators are not allowed in this match.");
+            return;
+        }
+
+        if !room.check_password(msg.password.as_deref()) {
+            tracing::info!("Incorrect room password.");
+            self.send_error(&msg.player_id, "Incorrect password.");
+            return;
+        }
+
+        if leave_current_room(self, &msg.player_id).is_some() {
+            tracing::info!("Player left their previous room to spectate this one.");
+        }
+
+        let room = self.rooms.get_mut(&room_id).unwrap();
+        room.spectators.insert(msg.player_id, msg.username);
+        self.player_rooms.insert(msg.player_id, room_id);
+
+        let marks = room.rules.marks();
+        let board = marks
+            .iter()
+            .map(|(key, mark)| {
+                let username = room
+                    .players
+                    .get(&mark.player_id)
+                    .cloned()
+                    .unwrap_or_default();
+                (key.clone(), username)
+            })
+            .collect();
+        let symbols = marks
+            .iter()
+            .map(|(key, mark)| (key.clone(), mark.symbol))
+            .collect();
+
+        let snapshot = MatchSnapshot {
+            match_id: room_id,
+            room_name: room.name.to_owned(),
+            status: room.status.to_owned(),
+            players: room.seated_players(),
+            current_turn: room.current_turn,
+            variant: room.options.variant,
+            board,
+            symbols,
+            state: room.rules.state(),
+        };
+
+        if let Some(addr) = self.sessions.get(&msg.player_id) {
+            let command = OutboundEvent::MatchSpectated(snapshot);
+            self.send_direct_message(addr, &command);
+            self.broadcast_presence();
+            self.notify_room_updated(&room_id);
+        }
+    }
+}
@@ -1,51 +1,69 @@
 use actix::prelude::*;
+use rand::Rng;
+use std::time::Instant;
 use uuid::Uuid;
 
 use crate::game_server::{
-    domain::TeamSymbol, CommandCategory, Commmand, GameRoom, GameRoomStatus, GameServer,
+    domain::{FirstMove, TeamSymbol},
+    events::stop_replay,
+    Empty, GameRoom, GameRoomStatus, GameServer, OutboundEvent,
 };
 
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct StartGame {
     pub player_id: Uuid,
-    pub room_id: Option<Uuid>,
-    pub team_symbol: Option<TeamSymbol>,
-}
-
-impl StartGame {
-    fn team_symbol_to_string(&self) -> String {
-        self.team_symbol
-            .map(|ts| ts.to_string())
-            .unwrap_or("".into())
-    }
 }
 
 impl Handler<StartGame> for GameServer {
     type Result = ();
 
-    #[tracing::instrument(name = "Game Start", skip_all, fields(player_id=%msg.player_id, team_symbol=%msg.team_symbol_to_string(), room_id))]
-    fn handle(&mut self, msg: StartGame, _: &mut Self::Context) -> Self::Result {
-        if let Some(room_id) = &msg.room_id {
-            tracing::Span::current().record("room_id", &room_id.to_string());
+    #[tracing::instrument(name = "Game Start", skip_all, fields(player_id=%msg.player_id, team_symbol, room_id))]
+    fn handle(&mut self, msg: StartGame, ctx: &mut Self::Context) -> Self::Result {
+        if let Some(&room_id) = self.player_rooms.get(&msg.player_id) {
+            tracing::Span::current().record("room_id", room_id.to_string());
 
-            if let Some(room) = find_waiting_game_room(self, room_id) {
-                if msg.team_symbol != Some(TeamSymbol::Cross) {
-                    tracing::info!("Circle player attempted to start the game, ignoring.");
+            if let Some(room) = find_waiting_game_room(self, &room_id) {
+                let team_symbol = room.symbol_of(&msg.player_id);
+                if let Some(team_symbol) = team_symbol {
+                    tracing::Span::current().record("team_symbol", team_symbol.to_string());
+                }
+                if team_symbol != Some(TeamSymbol::Cross) {
+                    tracing::info!("Only the Cross player can start the game, ignoring.");
                     return;
                 }
 
                 room.status = GameRoomStatus::Started;
+                room.history.clear();
+                room.turn_started = Instant::now();
+                room.last_activity = Instant::now();
+                room.current_turn = first_turn(room.options.first_move, room.options.player_count);
+                // Replays would interleave their moves with the game's
+                let seated: Vec<Uuid> = room.players.keys().copied().collect();
+                for player_id in seated {
+                    stop_replay(self, &player_id, ctx);
+                }
 
-                let command = Commmand::new_serialized(CommandCategory::GameStart, "");
-                self.send_message_all(room_id, &command);
+                let command = OutboundEvent::GameStart(Empty);
+                self.send_message_all(&room_id, &command);
+                self.send_message_spectators(&room_id, &command);
+                self.broadcast_presence();
+                self.notify_room_updated(&room_id);
             } else {
-                tracing::info!("Player is not in any room with 2 players and status waiting.");
+                tracing::info!("Player is not in any full room with status waiting.");
             }
         }
     }
 }
 
+fn first_turn(first_move: FirstMove, player_count: usize) -> TeamSymbol {
+    match first_move {
+        FirstMove::Creator => TeamSymbol::Cross,
+        FirstMove::Joiner => TeamSymbol::Circle,
+        FirstMove::Random => TeamSymbol::for_seat(rand::thread_rng().gen_range(0..player_count)),
+    }
+}
+
 fn find_waiting_game_room<'a>(
     server: &'a mut GameServer,
     room_id: &'a Uuid,
@@ -53,5 +71,5 @@ fn find_waiting_game_room<'a>(
     server
         .rooms
         .get_mut(room_id)
-        .filter(|r| r.status == GameRoomStatus::Waiting && r.players.iter().count() == 2)
+        .filter(|r| r.status == GameRoomStatus::Waiting && r.is_full())
 }
@@ -0,0 +1,49 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::stats::StatsSummary;
+use crate::game_server::{GameServer, OutboundEvent};
+
+/// Sends the player the stats of `target`, or their own when no target is given
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct GetStats {
+    pub player_id: Uuid,
+    pub target: Option<Uuid>,
+}
+
+impl Handler<GetStats> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Get stats", skip_all, fields(player_id=%msg.player_id))]
+    fn handle(&mut self, msg: GetStats, _: &mut Self::Context) -> Self::Result {
+        let target = msg.target.unwrap_or(msg.player_id);
+
+        match self.player_stats_summary(&target) {
+            Some(stats) => {
+                if let Some(addr) = self.sessions.get(&msg.player_id) {
+                    let command = OutboundEvent::Stats(stats);
+                    self.send_direct_message(addr, &command);
+                }
+            }
+            None => {
+                tracing::info!("Player not found.");
+                self.send_error(&msg.player_id, "Player not found.");
+            }
+        }
+    }
+}
+
+/// Stats of a player as served over HTTP, `None` for unknown players
+#[derive(Message, Debug)]
+#[rtype(result = "Option<StatsSummary>")]
+pub struct GetPlayerStats(pub Uuid);
+
+impl Handler<GetPlayerStats> for GameServer {
+    type Result = Option<StatsSummary>;
+
+    #[tracing::instrument(name = "Get player stats", skip_all, fields(player_id=%msg.0))]
+    fn handle(&mut self, msg: GetPlayerStats, _: &mut Self::Context) -> Self::Result {
+        self.player_stats_summary(&msg.0)
+    }
+}
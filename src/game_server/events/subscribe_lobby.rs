@@ -0,0 +1,27 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::{events::full_match_list, GameServer, OutboundEvent};
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SubscribeLobby {
+    pub player_id: Uuid,
+}
+
+impl Handler<SubscribeLobby> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Subscribe lobby", skip_all, fields(player_session_id=%msg.player_id))]
+    fn handle(&mut self, msg: SubscribeLobby, _: &mut Context<Self>) -> Self::Result {
+        if let Some(addr) = self.sessions.get(&msg.player_id) {
+            // Send every current room so the incremental events have something to apply to
+            let command = OutboundEvent::MatchList(full_match_list(self));
+            self.send_direct_message(addr, &command);
+
+            self.room_subscribers.insert(msg.player_id);
+        } else {
+            tracing::info!("User not found in server sessions");
+        }
+    }
+}
@@ -1,26 +1,22 @@
 use actix::prelude::*;
-use std::collections::HashMap;
+use std::time::Instant;
 use uuid::Uuid;
 
-use crate::game_server::commands::{CommandCategory, Commmand};
-use crate::game_server::domain::{TeamSymbol, TurnMove};
-use crate::game_server::{GameRoom, GameRoomStatus, GameServer};
+use crate::game_server::board::{Mark, MoveOutcome};
+use crate::game_server::domain::TurnInput;
+use crate::game_server::events::utils::{
+    change_turn, find_opponent, find_started_room_by_room_id, is_invalid_turn,
+    send_game_over_messages,
+};
+use crate::game_server::record::RecordedMove;
+use crate::game_server::rules::Move;
+use crate::game_server::{GameRoomStatus, GameServer, OutboundEvent};
 
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
 pub struct Turn {
     pub player_id: Uuid,
-    pub team_symbol: Option<TeamSymbol>,
-    pub turn_move: TurnMove,
-    pub room_id: Option<Uuid>,
-}
-
-impl Turn {
-    fn team_symbol_to_string(&self) -> String {
-        self.team_symbol
-            .map(|ts| ts.to_string())
-            .unwrap_or("".into())
-    }
+    pub turn_move: TurnInput,
 }
 
 impl Handler<Turn> for GameServer {
@@ -29,38 +25,78 @@ impl Handler<Turn> for GameServer {
     #[tracing::instrument(
         name = "Turn",
         skip_all,
-        fields(room_id, player_id=%msg.player_id, player_move=%msg.turn_move, player_team=%msg.team_symbol_to_string(), room_id)
+        fields(player_id=%msg.player_id, player_move=%msg.turn_move, player_team, room_id)
     )]
     fn handle(&mut self, msg: Turn, _: &mut Self::Context) -> Self::Result {
-        if let Some(room_id) = &msg.room_id {
-            if let Some(room) = find_started_room_by_room_id(self, room_id) {
+        if let Some(&room_id) = self.player_rooms.get(&msg.player_id) {
+            if let Some(room) = find_started_room_by_room_id(self, &room_id) {
                 tracing::Span::current().record("room_id", room_id.to_string());
 
-                if is_invalid_turn(room.current_turn, msg.team_symbol) {
+                let Some(team_symbol) = room.symbol_of(&msg.player_id) else {
+                    tracing::info!("Player is not part of this match.");
+                    return;
+                };
+                tracing::Span::current().record("player_team", team_symbol.to_string());
+
+                if is_invalid_turn(room.current_turn, team_symbol) {
                     tracing::info!("Invalid turn.");
                     return;
                 }
 
-                if is_duplicate_move(&msg.turn_move, &room.moves_made) {
-                    tracing::info!("Duplicate move.");
+                let mark = Mark {
+                    symbol: room.current_turn,
+                    player_id: msg.player_id,
+                };
+                let player_move = match room.rules.parse_move(&msg.turn_move, mark) {
+                    Ok(player_move) => player_move,
+                    Err(reason) => {
+                        tracing::info!("Invalid move.");
+                        self.send_error(&msg.player_id, &reason);
+                        return;
+                    }
+                };
+
+                if !room.rules.validate(&player_move) {
+                    tracing::info!("Illegal move.");
                     return;
                 }
 
-                room.moves_made.insert(msg.turn_move.clone(), msg.player_id);
-
-                if is_player_victory(room, &msg.player_id, &msg.turn_move) {
-                    tracing::info!("Game ended in victory");
-                    room.status = GameRoomStatus::Finished;
-                    send_messages_victory(self, room_id, &msg);
-                } else if is_game_tie(room) {
-                    tracing::info!("Game ended in tie");
-                    room.status = GameRoomStatus::Finished;
-                    send_messages_tie(self, room_id, &msg);
-                } else {
-                    change_turn(room);
-                    let command = Commmand::new_serialized(CommandCategory::Turn, &msg.turn_move);
-                    self.send_message(room_id, &command, msg.player_id.clone());
+                let move_time = room.turn_started.elapsed();
+                let turn_body = room.rules.apply(&player_move);
+                room.history.push(RecordedMove::from(&player_move));
+                room.last_activity = Instant::now();
+                let outcome = room.rules.outcome(&player_move);
+
+                match outcome {
+                    MoveOutcome::Victory | MoveOutcome::Defeat => {
+                        tracing::info!("Game ended in victory");
+                        room.status = GameRoomStatus::Finished;
+                        let winner_id = if outcome == MoveOutcome::Victory {
+                            msg.player_id
+                        } else {
+                            find_opponent(room, &msg.player_id)
+                        };
+                        send_messages_victory(self, &room_id, &player_move, &turn_body, winner_id);
+                        self.broadcast_presence();
+                        self.notify_room_updated(&room_id);
+                    }
+                    MoveOutcome::Tie => {
+                        tracing::info!("Game ended in tie");
+                        room.status = GameRoomStatus::Finished;
+                        send_messages_tie(self, &room_id, &player_move, &turn_body);
+                        self.broadcast_presence();
+                        self.notify_room_updated(&room_id);
+                    }
+                    MoveOutcome::Continue => {
+                        change_turn(room);
+                        send_turn_messages(self, &room_id, &player_move, &turn_body);
+                    }
                 }
+
+                self.player_stats
+                    .entry(msg.player_id)
+                    .or_default()
+                    .record_move(move_time);
             } else {
                 tracing::info!("Player is not in any room with status started.");
             }
@@ -68,110 +104,53 @@ impl Handler<Turn> for GameServer {
     }
 }
 
-fn send_messages_victory(server: &mut GameServer, room_id: &Uuid, msg: &Turn) {
-    let command = Commmand::new_serialized(CommandCategory::Turn, &msg.turn_move);
-    server.send_message(room_id, &command, msg.player_id.clone());
+/// Relay the move to everyone else in the room, written in the notation each of them picked
+/// Sends the move to everyone in the room, the mover included since the rules may have
+/// resolved it to something else than what was asked for
+fn send_turn_messages(
+    server: &GameServer,
+    room_id: &Uuid,
+    player_move: &Move,
+    turn_body: &serde_json::Value,
+) {
+    let Some(room) = server.rooms.get(room_id) else {
+        return;
+    };
 
-    if let Some(addr) = server.sessions.get(&msg.player_id) {
-        let command = Commmand::new_serialized(CommandCategory::GameOver, "victory");
+    let recipients = room.players.keys().chain(room.spectators.keys());
+    for id in recipients {
+        let Some(addr) = server.sessions.get(id) else {
+            continue;
+        };
+        let body = server
+            .move_notations
+            .get(id)
+            .and_then(|notation| room.rules.notated_move(player_move, *notation))
+            .unwrap_or_else(|| turn_body.clone());
+        let command = OutboundEvent::Turn(body);
         server.send_direct_message(addr, &command);
-
-        let command = Commmand::new_serialized(CommandCategory::GameOver, "defeat");
-        server.send_message(room_id, &command, msg.player_id.clone());
     }
 }
 
-fn send_messages_tie(server: &mut GameServer, room_id: &Uuid, msg: &Turn) {
-    let command = Commmand::new_serialized(CommandCategory::Turn, &msg.turn_move);
-    server.send_message(room_id, &command, msg.player_id.clone());
-
-    let command = Commmand::new_serialized(CommandCategory::GameOver, "tie");
-    server.send_message_all(room_id, &command);
-}
-
-fn find_started_room_by_room_id<'a>(
-    server: &'a mut GameServer,
-    room_id: &'a Uuid,
-) -> Option<&'a mut GameRoom> {
-    server
-        .rooms
-        .get_mut(room_id)
-        .filter(|r| r.status == GameRoomStatus::Started)
-}
-
-fn change_turn(room: &mut GameRoom) {
-    room.current_turn = if room.current_turn == TeamSymbol::Circle {
-        TeamSymbol::Cross
-    } else {
-        TeamSymbol::Circle
-    };
-}
-
-fn is_duplicate_move(new_move: &TurnMove, moves_made: &HashMap<TurnMove, Uuid>) -> bool {
-    return moves_made.get(new_move).is_some();
-}
-
-fn is_invalid_turn(current_turn: TeamSymbol, player_symbol: Option<TeamSymbol>) -> bool {
-    if player_symbol.is_none() {
-        return true;
-    }
-
-    if current_turn != player_symbol.unwrap() {
-        return true;
-    }
+fn send_messages_victory(
+    server: &mut GameServer,
+    room_id: &Uuid,
+    player_move: &Move,
+    turn_body: &serde_json::Value,
+    winner_id: Uuid,
+) {
+    send_turn_messages(server, room_id, player_move, turn_body);
 
-    false
+    send_game_over_messages(server, room_id, Some(winner_id));
 }
 
-fn is_player_victory(game_room: &GameRoom, player_id: &Uuid, player_move: &TurnMove) -> bool {
-    let player_moves: Vec<&TurnMove> = game_room
-        .moves_made
-        .iter()
-        .filter(|(_, v)| *v == player_id)
-        .map(|(k, _)| k)
-        .collect();
-
-    let player_move = player_move.to_string();
-    let mut move_iter = player_move.chars();
-
-    let row = move_iter.next().unwrap_or(' ');
-    let column = move_iter.next().unwrap_or(' ');
-
-    let is_row_victory = player_moves
-        .iter()
-        .filter(|pm| pm.to_string().starts_with(row))
-        .count()
-        == 3;
-
-    let is_column_victory = player_moves
-        .iter()
-        .filter(|pm| pm.to_string().ends_with(column))
-        .count()
-        == 3;
-
-    if is_row_victory || is_column_victory {
-        return true;
-    }
-
-    let is_diagonal_victory = player_moves
-        .iter()
-        .filter(|pm| ***pm == TurnMove::LL || ***pm == TurnMove::MM || ***pm == TurnMove::UR)
-        .count()
-        == 3;
-
-    let is_diagonal_victory_mirror = player_moves
-        .iter()
-        .filter(|pm| ***pm == TurnMove::UL || ***pm == TurnMove::MM || ***pm == TurnMove::LR)
-        .count()
-        == 3;
-
-    if is_diagonal_victory || is_diagonal_victory_mirror {
-        return true;
-    }
-
-    false
-}
+fn send_messages_tie(
+    server: &mut GameServer,
+    room_id: &Uuid,
+    player_move: &Move,
+    turn_body: &serde_json::Value,
+) {
+    send_turn_messages(server, room_id, player_move, turn_body);
 
-fn is_game_tie(game_room: &GameRoom) -> bool {
-    game_room.moves_made.iter().count() == 9
+    send_game_over_messages(server, room_id, None);
 }
@@ -0,0 +1,21 @@
+use actix::prelude::*;
+use uuid::Uuid;
+
+use crate::game_server::GameServer;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct UnsubscribeLobby {
+    pub player_id: Uuid,
+}
+
+impl Handler<UnsubscribeLobby> for GameServer {
+    type Result = ();
+
+    #[tracing::instrument(name = "Unsubscribe lobby", skip_all, fields(player_session_id=%msg.player_id))]
+    fn handle(&mut self, msg: UnsubscribeLobby, _: &mut Context<Self>) -> Self::Result {
+        if !self.room_subscribers.remove(&msg.player_id) {
+            tracing::info!("Player is not subscribed to the lobby.");
+        }
+    }
+}
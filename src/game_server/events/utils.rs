@@ -1,7 +1,9 @@
+use std::time::Instant;
 use uuid::Uuid;
 
 use crate::game_server::{
-    domain::TeamSymbol, CommandCategory, Commmand, GameRoom, GameRoomStatus, GameServer,
+    domain::TeamSymbol, record::GameResult, stats::GameOutcome, Empty, GameOver, GameRecorded,
+    GameRoom, GameRoomStatus, GameServer, OutboundEvent, PlayerResult, RoomClosed,
 };
 
 pub enum ShouldDeleteRoom {
@@ -17,31 +19,188 @@ pub fn handle_potential_room_deletion(
 ) {
     match should_delete_room {
         ShouldDeleteRoom::No => {
-            let command = Commmand::new(CommandCategory::PlayerLeft, "".to_string());
-            let result = serde_json::to_string(&command).unwrap_or("".into());
-
-            server.send_message(room_id, &result, player_id.clone());
+            let command = OutboundEvent::PlayerLeft(Empty);
+            server.send_message(room_id, &command, *player_id);
+            server.send_message_spectators(room_id, &command);
         }
         ShouldDeleteRoom::Yes => {
-            server.rooms.remove(room_id);
+            let command = OutboundEvent::RoomClosed(RoomClosed {
+                reason: "All players left.".to_string(),
+            });
+            server.send_message_spectators(room_id, &command);
+            server.remove_room(room_id);
+        }
+    }
+}
+
+/// Takes the player out of the room they are playing or spectating in, returning its id
+pub fn leave_current_room(server: &mut GameServer, player_id: &Uuid) -> Option<Uuid> {
+    let room_id = server.player_rooms.remove(player_id)?;
+    record_forfeit(server, &room_id, player_id);
+
+    if let Some(room) = server.rooms.get_mut(&room_id) {
+        if remove_spectator_from_room(room, player_id) {
+            tracing::info!("Spectator left the room.");
+        } else {
+            let should_delete_room = remove_player_from_room(room, player_id);
+            handle_potential_room_deletion(should_delete_room, server, player_id, &room_id);
         }
+        server.notify_room_updated(&room_id);
     }
+
+    Some(room_id)
+}
+
+/// Returns whether the session was spectating the room, in which case the match is left untouched
+pub fn remove_spectator_from_room(room: &mut GameRoom, player_id: &Uuid) -> bool {
+    room.spectators.remove(player_id).is_some()
 }
 
 pub fn remove_player_from_room(room: &mut GameRoom, player_id: &Uuid) -> ShouldDeleteRoom {
-    room.players.remove(player_id);
-    if room.players.len() > 0 {
+    room.remove_player(player_id);
+    if !room.players.is_empty() {
         if room.status != GameRoomStatus::Finished {
             reset_room(room);
         }
-        return ShouldDeleteRoom::No;
+        ShouldDeleteRoom::No
     } else {
-        return ShouldDeleteRoom::Yes;
+        ShouldDeleteRoom::Yes
     }
 }
 
 fn reset_room(room: &mut GameRoom) {
-    room.moves_made.clear();
+    room.rules.reset();
     room.status = GameRoomStatus::Waiting;
     room.current_turn = TeamSymbol::Cross;
+    room.history.clear();
+}
+
+pub fn find_started_room_by_room_id<'a>(
+    server: &'a mut GameServer,
+    room_id: &'a Uuid,
+) -> Option<&'a mut GameRoom> {
+    server
+        .rooms
+        .get_mut(room_id)
+        .filter(|r| r.status == GameRoomStatus::Started)
+}
+
+pub fn find_opponent(room: &GameRoom, player_id: &Uuid) -> Uuid {
+    room.players
+        .keys()
+        .find(|id| *id != player_id)
+        .copied()
+        .unwrap_or(*player_id)
+}
+
+pub fn change_turn(room: &mut GameRoom) {
+    room.current_turn = room.current_turn.next(room.options.player_count);
+    room.turn_started = Instant::now();
+}
+
+pub fn is_invalid_turn(current_turn: TeamSymbol, player_symbol: TeamSymbol) -> bool {
+    current_turn != player_symbol
+}
+
+/// Tells players whether they won, lost or tied and spectators who the winner is, then
+/// updates their stats and stores the game's record
+pub fn send_game_over_messages(server: &mut GameServer, room_id: &Uuid, winner_id: Option<Uuid>) {
+    let Some(winner_id) = winner_id else {
+        let command = OutboundEvent::GameOver(GameOver::Result(PlayerResult::Tie));
+        server.send_message_all(room_id, &command);
+
+        let command = OutboundEvent::GameOver(GameOver::Winner { winner: None });
+        server.send_message_spectators(room_id, &command);
+        record_player_stats(server, room_id, None);
+        record_finished_game(server, room_id, None);
+        return;
+    };
+
+    let winner = server
+        .rooms
+        .get(room_id)
+        .and_then(|room| room.players.get(&winner_id))
+        .cloned();
+    let command = OutboundEvent::GameOver(GameOver::Winner { winner });
+    server.send_message_spectators(room_id, &command);
+
+    if let Some(addr) = server.sessions.get(&winner_id) {
+        let command = OutboundEvent::GameOver(GameOver::Result(PlayerResult::Victory));
+        server.send_direct_message(addr, &command);
+    }
+
+    let command = OutboundEvent::GameOver(GameOver::Result(PlayerResult::Defeat));
+    server.send_message(room_id, &command, winner_id);
+    record_player_stats(server, room_id, Some(winner_id));
+    record_finished_game(server, room_id, Some(winner_id));
+}
+
+/// Adds the game that just finished in the room to the stats of everyone who played it
+fn record_player_stats(server: &mut GameServer, room_id: &Uuid, winner_id: Option<Uuid>) {
+    let Some(room) = server.rooms.get(room_id) else {
+        return;
+    };
+
+    for player_id in room.players.keys() {
+        let outcome = match winner_id {
+            None => GameOutcome::Tie,
+            Some(winner_id) if winner_id == *player_id => GameOutcome::Win,
+            Some(_) => GameOutcome::Loss,
+        };
+        server
+            .player_stats
+            .entry(*player_id)
+            .or_default()
+            .record_game(room.symbol_of(player_id), outcome);
+    }
+}
+
+/// Counts a player leaving a started game as a forfeit, their opponent is credited the win
+/// when only one is left to claim it
+pub fn record_forfeit(server: &mut GameServer, room_id: &Uuid, player_id: &Uuid) {
+    let Some(room) = server
+        .rooms
+        .get(room_id)
+        .filter(|r| r.status == GameRoomStatus::Started && r.players.contains_key(player_id))
+    else {
+        return;
+    };
+
+    server
+        .player_stats
+        .entry(*player_id)
+        .or_default()
+        .record_game(room.symbol_of(player_id), GameOutcome::Forfeit);
+
+    if let [opponent_id] = room
+        .players
+        .keys()
+        .filter(|id| *id != player_id)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        server
+            .player_stats
+            .entry(**opponent_id)
+            .or_default()
+            .record_game(room.symbol_of(opponent_id), GameOutcome::Win);
+    }
+}
+
+/// Stores the record of the game that just finished in the room and tells everyone in it
+/// the id it can be downloaded with
+fn record_finished_game(server: &mut GameServer, room_id: &Uuid, winner_id: Option<Uuid>) {
+    let Some(room) = server.rooms.get(room_id) else {
+        return;
+    };
+
+    let result = winner_id
+        .and_then(|winner_id| room.symbol_of(&winner_id).map(GameResult::Winner))
+        .unwrap_or(GameResult::Draw);
+    let record = room.game_record(result);
+    let game_id = server.store_game_record(record);
+
+    let command = OutboundEvent::GameRecorded(GameRecorded { game_id });
+    server.send_message_all(room_id, &command);
+    server.send_message_spectators(room_id, &command);
 }
@@ -1,7 +1,11 @@
+pub mod board;
 mod commands;
 pub mod domain;
 pub mod events;
+pub mod record;
+pub mod rules;
 mod server;
+pub mod stats;
 
 pub use commands::*;
-pub use server::{GameRoom, GameRoomStatus, GameServer, GameState, ServerMessage};
+pub use server::{GameRoom, GameRoomStatus, GameServer, GameState, Replay, ServerMessage};
@@ -0,0 +1,823 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::game_server::board::{Cell, Mark, MoveOutcome};
+use crate::game_server::commands::{CollapseResult, OutboundEvent};
+use crate::game_server::domain::{MatchOptions, MoveNotation, TeamSymbol, TimeControl, Variant};
+use crate::game_server::rules::{rules_for, Move, Resolution};
+
+/// How a finished game ended, written as the winning symbol's letter or `Draw`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum GameResult {
+    Winner(TeamSymbol),
+    Draw,
+}
+
+impl std::fmt::Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Winner(symbol) => write!(f, "{}", symbol.letter()),
+            Self::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
+impl std::str::FromStr for GameResult {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Draw" => Ok(Self::Draw),
+            _ => parse_symbol(value).map(Self::Winner),
+        }
+    }
+}
+
+/// One ply of a recorded game
+///
+/// Placements are written as the symbol placed followed by its cells, prefixed with the
+/// board they are played on when it isn't the first one (`X 1,1`, `O 4/0,2`, `X 0,0 1,2`),
+/// collapses as the symbol of the player choosing followed by `*` and the cell (`O *1,2`).
+#[derive(Debug, PartialEq, Clone)]
+pub enum RecordedMove {
+    Place {
+        symbol: TeamSymbol,
+        board: usize,
+        cells: Vec<Cell>,
+    },
+    Collapse {
+        symbol: TeamSymbol,
+        cell: Cell,
+    },
+}
+
+impl From<&Move> for RecordedMove {
+    fn from(player_move: &Move) -> Self {
+        RecordedMove::Place {
+            symbol: player_move.mark.symbol,
+            board: player_move.board,
+            cells: player_move.cells.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for RecordedMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Place {
+                symbol,
+                board,
+                cells,
+            } => {
+                write!(f, "{}", symbol.letter())?;
+                for (index, cell) in cells.iter().enumerate() {
+                    if index == 0 && *board != 0 {
+                        write!(f, " {}/{}", board, cell)?;
+                    } else {
+                        write!(f, " {}", cell)?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Collapse { symbol, cell } => write!(f, "{} *{}", symbol.letter(), cell),
+        }
+    }
+}
+
+impl std::str::FromStr for RecordedMove {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.split_whitespace();
+        let symbol = parse_symbol(parts.next().unwrap_or_default())?;
+        let targets: Vec<&str> = parts.collect();
+
+        if let [target] = targets.as_slice() {
+            if let Some(cell) = target.strip_prefix('*') {
+                return Ok(Self::Collapse {
+                    symbol,
+                    cell: parse_cell(cell)?,
+                });
+            }
+        }
+
+        let Some((first, rest)) = targets.split_first() else {
+            return Err(format!("Move \"{}\" has no cells.", value));
+        };
+        let (board, first) = match first.split_once('/') {
+            Some((board, cell)) => (parse_number(board)?, cell),
+            None => (0, *first),
+        };
+        let cells = std::iter::once(first)
+            .chain(rest.iter().copied())
+            .map(parse_cell)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::Place {
+            symbol,
+            board,
+            cells,
+        })
+    }
+}
+
+/// A finished game as written in its text record: `[Name "value"]` headers, a blank line,
+/// then one numbered line per move
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    /// Name of the room the game was played in
+    pub event: String,
+    /// Day the game finished on, as `YYYY.MM.DD`
+    pub date: String,
+    /// Username of each seat, in seat order
+    pub players: Vec<(TeamSymbol, String)>,
+    /// Options the game was played with, only those written in headers survive an import
+    pub options: MatchOptions,
+    pub result: GameResult,
+    pub moves: Vec<RecordedMove>,
+}
+
+impl GameRecord {
+    /// Plays every move again with the rules of the recorded variant, returning the messages
+    /// live clients were sent for each of them with moves written in `notation`, fails on the
+    /// first move that couldn't have been played: an illegal one, one played out of turn or
+    /// one played after the game ended
+    pub fn replay(&self, notation: MoveNotation) -> Result<Vec<OutboundEvent>, String> {
+        self.play(notation).map(|(messages, _)| messages)
+    }
+
+    /// Checks a record written elsewhere: every move must be legal, and the game must end
+    /// with its last move the way its `Result` header says
+    pub fn verify(&self) -> Result<(), String> {
+        let (_, ending) = self.play(MoveNotation::default())?;
+        let Some(ending) = ending else {
+            return Err("The game isn't finished after its last move.".into());
+        };
+
+        // Wild marks don't tell who placed them, so only whether someone won can be checked
+        let matches = match (ending, self.result) {
+            (GameResult::Winner(_), GameResult::Winner(_))
+                if !self.options.variant.marks_show_mover() =>
+            {
+                true
+            }
+            (ending, result) => ending == result,
+        };
+        if !matches {
+            return Err(format!(
+                "Result \"{}\" doesn't match the game, which ended with \"{}\".",
+                self.result, ending
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Replays the moves, returning their messages and how the game ended if it did
+    fn play(
+        &self,
+        notation: MoveNotation,
+    ) -> Result<(Vec<OutboundEvent>, Option<GameResult>), String> {
+        let mut rules = rules_for(&self.options);
+        let mut messages = Vec::with_capacity(self.moves.len());
+        // Who moves first depends on options records don't keep, so the first move sets it
+        let mut turn: Option<TeamSymbol> = None;
+        let mut ending = None;
+
+        for (index, recorded_move) in self.moves.iter().enumerate() {
+            let invalid = |reason: &str| {
+                Err(format!(
+                    "Move {} \"{}\" {}.",
+                    index + 1,
+                    recorded_move,
+                    reason
+                ))
+            };
+
+            if ending.is_some() {
+                return invalid("is played after the game ended");
+            }
+
+            let mover = match recorded_move {
+                RecordedMove::Place { symbol, .. } | RecordedMove::Collapse { symbol, .. } => {
+                    *symbol
+                }
+            };
+            let checks_turn = self.options.variant.marks_show_mover()
+                || matches!(recorded_move, RecordedMove::Collapse { .. });
+            if checks_turn && turn.is_some_and(|turn| turn != mover) {
+                return invalid("is played out of turn");
+            }
+
+            let message = match recorded_move {
+                RecordedMove::Place {
+                    symbol,
+                    board,
+                    cells,
+                } => {
+                    let player_move = Move {
+                        board: *board,
+                        cells: cells.clone(),
+                        mark: Mark {
+                            symbol: *symbol,
+                            player_id: uuid::Uuid::nil(),
+                        },
+                    };
+                    rules.validate(&player_move).then(|| {
+                        let body = rules.apply(&player_move);
+                        let body = rules.notated_move(&player_move, notation).unwrap_or(body);
+                        // Collapses are chosen by the player whose turn it is, so only
+                        // placements pass the turn on
+                        let current = turn.unwrap_or(mover);
+                        let next = current.next(self.options.player_count);
+                        ending = match rules.outcome(&player_move) {
+                            MoveOutcome::Victory => Some(GameResult::Winner(current)),
+                            MoveOutcome::Defeat => Some(GameResult::Winner(next)),
+                            MoveOutcome::Tie => Some(GameResult::Draw),
+                            MoveOutcome::Continue => None,
+                        };
+                        turn = Some(next);
+                        OutboundEvent::Turn(body)
+                    })
+                }
+                RecordedMove::Collapse { cell, .. } => {
+                    rules.resolve_pending(*cell).map(|outcome| {
+                        let scores = match outcome {
+                            Resolution::Won { winner, scores } => {
+                                ending = Some(GameResult::Winner(winner.symbol));
+                                scores
+                            }
+                            Resolution::Tied => {
+                                ending = Some(GameResult::Draw);
+                                serde_json::Value::Null
+                            }
+                            Resolution::Undecided => serde_json::Value::Null,
+                        };
+                        OutboundEvent::Collapse(CollapseResult {
+                            cell: *cell,
+                            board: rules.state(),
+                            scores,
+                        })
+                    })
+                }
+            };
+
+            let Some(message) = message else {
+                return invalid("is illegal");
+            };
+            messages.push(message);
+        }
+
+        Ok((messages, ending))
+    }
+}
+
+impl std::fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Event \"{}\"]", header_value(&self.event))?;
+        writeln!(f, "[Date \"{}\"]", self.date)?;
+        for (symbol, username) in &self.players {
+            writeln!(f, "[{} \"{}\"]", symbol, header_value(username))?;
+        }
+        writeln!(f, "[Variant \"{:?}\"]", self.options.variant)?;
+        if !self.options.variant.has_fixed_board() {
+            writeln!(
+                f,
+                "[Board \"{}x{}\"]",
+                self.options.board_width(),
+                self.options.board_height()
+            )?;
+            writeln!(f, "[WinLength \"{}\"]", self.options.win_length())?;
+        }
+        if let Some(board_count) = self.options.board_count {
+            writeln!(f, "[Boards \"{}\"]", board_count)?;
+        }
+        match &self.options.time_control {
+            Some(time_control) => writeln!(
+                f,
+                "[TimeControl \"{}+{}\"]",
+                time_control.initial_seconds, time_control.increment_seconds
+            )?,
+            None => writeln!(f, "[TimeControl \"-\"]")?,
+        }
+        writeln!(f, "[Result \"{}\"]", self.result)?;
+
+        writeln!(f)?;
+        for (index, recorded_move) in self.moves.iter().enumerate() {
+            writeln!(f, "{}. {}", index + 1, recorded_move)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for GameRecord {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut event = None;
+        let mut date = None;
+        let mut players = vec![];
+        let mut variant = None;
+        let mut board = None;
+        let mut win_length = None;
+        let mut board_count = None;
+        let mut time_control = None;
+        let mut result = None;
+        let mut moves = vec![];
+
+        for line in value.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(header) = line.strip_prefix('[') {
+                let (name, value) = parse_header(header)?;
+                match name {
+                    "Event" => event = Some(value.to_string()),
+                    "Date" => date = Some(value.to_string()),
+                    "Cross" | "Circle" | "Triangle" => {
+                        players.push((parse_symbol(name)?, value.to_string()))
+                    }
+                    "Variant" => {
+                        variant = Some(
+                            serde_json::from_value::<Variant>(serde_json::json!(value))
+                                .map_err(|_| format!("Unknown variant \"{}\".", value))?,
+                        )
+                    }
+                    "Board" => {
+                        let (width, height) = value
+                            .split_once('x')
+                            .ok_or_else(|| format!("Invalid board \"{}\".", value))?;
+                        board = Some((parse_number(width)?, parse_number(height)?));
+                    }
+                    "WinLength" => win_length = Some(parse_number(value)?),
+                    "Boards" => board_count = Some(parse_number(value)?),
+                    "TimeControl" => {
+                        time_control = match value.split_once('+') {
+                            Some((initial, increment)) => Some(TimeControl {
+                                initial_seconds: parse_number(initial)?,
+                                increment_seconds: parse_number(increment)?,
+                            }),
+                            None => None,
+                        }
+                    }
+                    "Result" => result = Some(value.parse()?),
+                    // Unknown headers are kept out of the way of future additions
+                    _ => {}
+                }
+            } else {
+                let (number, recorded_move) = line
+                    .split_once('.')
+                    .ok_or_else(|| format!("Invalid move line \"{}\".", line))?;
+                if parse_number::<usize>(number)? != moves.len() + 1 {
+                    return Err(format!("Move {} is out of order.", number));
+                }
+                moves.push(recorded_move.parse()?);
+            }
+        }
+
+        let variant = variant.ok_or("Missing Variant header.")?;
+        // Records written before fixed board variants left these headers out still carry them
+        if variant.has_fixed_board() {
+            board = None;
+            win_length = None;
+        }
+        let (width, height) = board.unwrap_or((3, 3));
+        let options = MatchOptions {
+            variant,
+            board_size: width,
+            board_height: (height != width).then_some(height),
+            win_length,
+            board_count,
+            player_count: players.len().max(2),
+            time_control,
+            ..Default::default()
+        };
+        options.validate()?;
+
+        Ok(GameRecord {
+            event: event.unwrap_or_default(),
+            date: date.unwrap_or_default(),
+            players,
+            options,
+            result: result.ok_or("Missing Result header.")?,
+            moves,
+        })
+    }
+}
+
+/// Today's date in the `YYYY.MM.DD` form used by the `Date` header
+pub fn record_date() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or_default() as i64;
+
+    // Civil calendar from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}.{:02}.{:02}", year, month, day)
+}
+
+/// Header value as written in a record, quotes and line breaks would end the header early
+fn header_value(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == '"' { '\'' } else { c })
+        .collect()
+}
+
+fn parse_header(header: &str) -> Result<(&str, &str), String> {
+    let invalid = || format!("Invalid header \"[{}\".", header);
+    let (name, value) = header
+        .strip_suffix(']')
+        .and_then(|header| header.split_once(' '))
+        .ok_or_else(invalid)?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+
+    Ok((name, value))
+}
+
+fn parse_symbol(value: &str) -> Result<TeamSymbol, String> {
+    match value {
+        "X" | "Cross" => Ok(TeamSymbol::Cross),
+        "O" | "Circle" => Ok(TeamSymbol::Circle),
+        "T" | "Triangle" => Ok(TeamSymbol::Triangle),
+        _ => Err(format!("Unknown symbol \"{}\".", value)),
+    }
+}
+
+fn parse_cell(value: &str) -> Result<Cell, String> {
+    let (row, column) = value
+        .split_once(',')
+        .ok_or_else(|| format!("Invalid cell \"{}\".", value))?;
+
+    Ok(Cell::new(parse_number(row)?, parse_number(column)?))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid number \"{}\".", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_date, GameRecord, GameResult, RecordedMove};
+    use crate::game_server::board::Cell;
+    use crate::game_server::commands::OutboundEvent;
+    use crate::game_server::domain::{
+        MatchOptions, MoveNotation, TeamSymbol, TimeControl, Variant,
+    };
+
+    fn place(symbol: TeamSymbol, board: usize, cells: &[(usize, usize)]) -> RecordedMove {
+        RecordedMove::Place {
+            symbol,
+            board,
+            cells: cells.iter().map(|(r, c)| Cell::new(*r, *c)).collect(),
+        }
+    }
+
+    fn standard_record() -> GameRecord {
+        GameRecord {
+            event: "room".into(),
+            date: "2024.03.09".into(),
+            players: vec![
+                (TeamSymbol::Cross, "alice".into()),
+                (TeamSymbol::Circle, "bob".into()),
+            ],
+            options: MatchOptions {
+                time_control: Some(TimeControl {
+                    initial_seconds: 60,
+                    increment_seconds: 5,
+                }),
+                ..Default::default()
+            },
+            result: GameResult::Winner(TeamSymbol::Cross),
+            moves: vec![
+                place(TeamSymbol::Cross, 0, &[(1, 1)]),
+                place(TeamSymbol::Circle, 0, &[(0, 0)]),
+                place(TeamSymbol::Cross, 0, &[(0, 2)]),
+                place(TeamSymbol::Circle, 0, &[(2, 2)]),
+                place(TeamSymbol::Cross, 0, &[(2, 0)]),
+            ],
+        }
+    }
+
+    #[test]
+    pub fn records_are_written_with_headers_and_numbered_moves() {
+        let expected = "[Event \"room\"]\n\
+            [Date \"2024.03.09\"]\n\
+            [Cross \"alice\"]\n\
+            [Circle \"bob\"]\n\
+            [Variant \"Standard\"]\n\
+            [Board \"3x3\"]\n\
+            [WinLength \"3\"]\n\
+            [TimeControl \"60+5\"]\n\
+            [Result \"X\"]\n\
+            \n\
+            1. X 1,1\n\
+            2. O 0,0\n\
+            3. X 0,2\n\
+            4. O 2,2\n\
+            5. X 2,0\n";
+
+        assert_eq!(standard_record().to_string(), expected);
+    }
+
+    #[test]
+    pub fn records_round_trip() {
+        let record = standard_record();
+        let parsed: GameRecord = record.to_string().parse().unwrap();
+
+        assert_eq!(parsed.to_string(), record.to_string());
+        assert_eq!(parsed.players, record.players);
+        assert_eq!(parsed.moves, record.moves);
+        assert_eq!(parsed.result, record.result);
+        assert_eq!(parsed.options.time_control, record.options.time_control);
+
+        let replay = parsed.replay(MoveNotation::Code).unwrap();
+
+        assert_eq!(replay.len(), 5);
+        assert_eq!(replay[0], OutboundEvent::Turn("MM".into()));
+    }
+
+    #[test]
+    pub fn headers_with_quotes_and_line_breaks_round_trip() {
+        let record = GameRecord {
+            event: "room\n\n1. O 0,0".into(),
+            players: vec![
+                (TeamSymbol::Cross, "ali\"ce\"]\r\n[Result \"O\"]".into()),
+                (TeamSymbol::Circle, "b\tob".into()),
+            ],
+            ..standard_record()
+        };
+        let parsed: GameRecord = record.to_string().parse().unwrap();
+
+        assert_eq!(parsed.event, "room1. O 0,0");
+        assert_eq!(
+            parsed.players,
+            vec![
+                (TeamSymbol::Cross, "ali'ce'][Result 'O']".into()),
+                (TeamSymbol::Circle, "bob".into()),
+            ]
+        );
+        assert_eq!(parsed.result, record.result);
+        assert_eq!(parsed.moves, record.moves);
+        assert_eq!(parsed.to_string(), record.to_string());
+        assert_eq!(parsed.verify(), Ok(()));
+    }
+
+    #[test]
+    pub fn variant_moves_round_trip() {
+        let moves = vec![
+            place(TeamSymbol::Cross, 4, &[(0, 2)]),
+            place(TeamSymbol::Circle, 0, &[(0, 0), (1, 2)]),
+            RecordedMove::Collapse {
+                symbol: TeamSymbol::Circle,
+                cell: Cell::new(1, 2),
+            },
+        ];
+
+        for recorded_move in moves {
+            assert_eq!(recorded_move.to_string().parse(), Ok(recorded_move));
+        }
+    }
+
+    #[test]
+    pub fn every_variant_round_trips() {
+        let records = [
+            (MatchOptions::default(), standard_record().moves),
+            (
+                MatchOptions {
+                    variant: Variant::Ultimate,
+                    ..Default::default()
+                },
+                vec![
+                    place(TeamSymbol::Cross, 4, &[(0, 2)]),
+                    place(TeamSymbol::Circle, 2, &[(1, 1)]),
+                ],
+            ),
+            (
+                MatchOptions {
+                    variant: Variant::Misere,
+                    ..Default::default()
+                },
+                vec![
+                    place(TeamSymbol::Cross, 0, &[(1, 1)]),
+                    place(TeamSymbol::Circle, 0, &[(0, 0)]),
+                ],
+            ),
+            (
+                MatchOptions {
+                    variant: Variant::Gravity,
+                    board_size: 7,
+                    board_height: Some(6),
+                    win_length: Some(4),
+                    ..Default::default()
+                },
+                vec![
+                    place(TeamSymbol::Cross, 0, &[(5, 3)]),
+                    place(TeamSymbol::Circle, 0, &[(4, 3)]),
+                ],
+            ),
+            (
+                MatchOptions {
+                    variant: Variant::Wild,
+                    ..Default::default()
+                },
+                vec![
+                    place(TeamSymbol::Cross, 0, &[(1, 1)]),
+                    place(TeamSymbol::Cross, 0, &[(0, 0)]),
+                ],
+            ),
+            (
+                MatchOptions {
+                    variant: Variant::Notakto,
+                    board_count: Some(2),
+                    ..Default::default()
+                },
+                vec![
+                    place(TeamSymbol::Cross, 0, &[(1, 1)]),
+                    place(TeamSymbol::Cross, 1, &[(0, 0)]),
+                ],
+            ),
+            (
+                MatchOptions {
+                    variant: Variant::Quantum,
+                    ..Default::default()
+                },
+                vec![
+                    place(TeamSymbol::Cross, 0, &[(0, 0), (1, 1)]),
+                    place(TeamSymbol::Circle, 0, &[(1, 1), (2, 2)]),
+                    place(TeamSymbol::Cross, 0, &[(2, 2), (0, 0)]),
+                    RecordedMove::Collapse {
+                        symbol: TeamSymbol::Circle,
+                        cell: Cell::new(0, 0),
+                    },
+                ],
+            ),
+        ];
+
+        for (options, moves) in records {
+            let record = GameRecord {
+                options,
+                moves,
+                result: GameResult::Draw,
+                ..standard_record()
+            };
+            let parsed: GameRecord = record.to_string().parse().unwrap_or_else(|e| {
+                panic!("{:?} record failed to parse: {}", record.options.variant, e)
+            });
+
+            assert_eq!(parsed.to_string(), record.to_string());
+            assert_eq!(parsed.options.variant, record.options.variant);
+            assert_eq!(parsed.options.win_length(), record.options.win_length());
+            assert_eq!(parsed.options.board_count, record.options.board_count);
+            assert_eq!(parsed.moves, record.moves);
+            assert!(
+                parsed.replay(MoveNotation::Code).is_ok(),
+                "{:?} record failed to replay",
+                record.options.variant
+            );
+        }
+    }
+
+    #[test]
+    pub fn fixed_board_records_ignore_board_headers() {
+        let record = "[Variant \"Quantum\"]\n\
+            [Board \"3x3\"]\n\
+            [WinLength \"3\"]\n\
+            [Result \"Draw\"]\n\
+            \n\
+            1. X 0,0 1,1\n";
+
+        assert!(record.parse::<GameRecord>().is_ok());
+    }
+
+    #[test]
+    pub fn moves_out_of_turn_are_rejected() {
+        let mut record = standard_record();
+        record.moves[1] = place(TeamSymbol::Cross, 0, &[(0, 0)]);
+
+        assert_eq!(
+            record.replay(MoveNotation::Code),
+            Err("Move 2 \"X 0,0\" is played out of turn.".into())
+        );
+    }
+
+    #[test]
+    pub fn floating_gravity_moves_are_rejected() {
+        let record = GameRecord {
+            options: MatchOptions {
+                variant: Variant::Gravity,
+                board_size: 7,
+                board_height: Some(6),
+                ..Default::default()
+            },
+            moves: vec![place(TeamSymbol::Cross, 0, &[(2, 3)])],
+            ..standard_record()
+        };
+
+        assert_eq!(
+            record.replay(MoveNotation::Code),
+            Err("Move 1 \"X 2,3\" is illegal.".into())
+        );
+    }
+
+    #[test]
+    pub fn moves_after_the_game_ended_are_rejected() {
+        let mut record = standard_record();
+        record.moves.push(place(TeamSymbol::Circle, 0, &[(1, 0)]));
+
+        assert_eq!(
+            record.replay(MoveNotation::Code),
+            Err("Move 6 \"O 1,0\" is played after the game ended.".into())
+        );
+    }
+
+    #[test]
+    pub fn records_are_verified_against_their_result() {
+        assert_eq!(standard_record().verify(), Ok(()));
+
+        let mut unfinished = standard_record();
+        unfinished.moves.pop();
+
+        assert_eq!(
+            unfinished.verify(),
+            Err("The game isn't finished after its last move.".into())
+        );
+
+        let mismatched = GameRecord {
+            result: GameResult::Winner(TeamSymbol::Circle),
+            ..standard_record()
+        };
+
+        assert_eq!(
+            mismatched.verify(),
+            Err("Result \"O\" doesn't match the game, which ended with \"X\".".into())
+        );
+    }
+
+    #[test]
+    pub fn rectangular_boards_round_trip() {
+        let record = GameRecord {
+            options: MatchOptions {
+                variant: Variant::Gravity,
+                board_size: 7,
+                board_height: Some(6),
+                ..Default::default()
+            },
+            moves: vec![place(TeamSymbol::Cross, 0, &[(5, 3)])],
+            result: GameResult::Draw,
+            ..standard_record()
+        };
+        let parsed: GameRecord = record.to_string().parse().unwrap();
+
+        assert_eq!(parsed.options.variant, Variant::Gravity);
+        assert_eq!(parsed.options.board_height(), 6);
+        assert_eq!(parsed.to_string(), record.to_string());
+    }
+
+    #[test]
+    pub fn illegal_records_are_rejected() {
+        let mut record = standard_record();
+        record.moves[1] = place(TeamSymbol::Circle, 0, &[(1, 1)]);
+        let parsed: GameRecord = record.to_string().parse().unwrap();
+
+        assert_eq!(
+            parsed.replay(MoveNotation::Code),
+            Err("Move 2 \"O 1,1\" is illegal.".into())
+        );
+        assert!("[Result \"X\"]\n\n1. X 1,1".parse::<GameRecord>().is_err());
+        assert!(standard_record()
+            .to_string()
+            .replace("2. O", "3. O")
+            .parse::<GameRecord>()
+            .is_err());
+    }
+
+    #[test]
+    pub fn record_dates_use_the_header_format() {
+        let date = record_date();
+
+        assert_eq!(date.len(), 10);
+        assert_eq!(&date[4..5], ".");
+        assert_eq!(&date[7..8], ".");
+    }
+}
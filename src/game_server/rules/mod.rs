@@ -0,0 +1,96 @@
+mod notakto;
+mod quantum;
+mod standard;
+mod ultimate;
+
+pub use notakto::NotaktoBoards;
+pub use quantum::QuantumBoard;
+pub use standard::StandardRules;
+pub use ultimate::{SubBoardStatus, UltimateBoard};
+
+use crate::game_server::board::{Cell, Mark, MoveOutcome};
+use crate::game_server::domain::{MatchOptions, MoveNotation, TurnInput, Variant};
+
+/// A move once parsed by the room's rules, before it is checked against the board
+#[derive(Debug, PartialEq, Clone)]
+pub struct Move {
+    /// Board the move is played on, always 0 for variants with a single board
+    pub board: usize,
+    /// Cells taken by the move, a quantum move takes two unless only one is left
+    pub cells: Vec<Cell>,
+    pub mark: Mark,
+}
+
+/// How a game stands once the choice a move left pending was made
+#[derive(Debug, PartialEq, Clone)]
+pub enum Resolution {
+    Undecided,
+    Won {
+        winner: Mark,
+        /// Variant specific final scores
+        scores: serde_json::Value,
+    },
+    Tied,
+}
+
+/// Rules of a variant along with the board state they are played on
+pub trait GameRules: std::fmt::Debug + Send {
+    /// Puts the board back the way a new match starts
+    fn reset(&mut self);
+
+    /// Reads a player's input as a move, failing when it doesn't fit the variant's move format
+    fn parse_move(&self, input: &TurnInput, mark: Mark) -> Result<Move, String>;
+
+    /// Whether the move can be played on the board as it is
+    fn validate(&self, player_move: &Move) -> bool;
+
+    /// Plays a validated move, returning the body relayed to the room in `Turn` messages
+    fn apply(&mut self, player_move: &Move) -> serde_json::Value;
+
+    /// Outcome once `last_move` was applied, seen from the player who made it
+    fn outcome(&self, last_move: &Move) -> MoveOutcome;
+
+    /// `Turn` body for an applied move written in another notation, `None` when the variant
+    /// only has the one returned by [`GameRules::apply`]
+    fn notated_move(
+        &self,
+        _player_move: &Move,
+        _notation: MoveNotation,
+    ) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Variant specific board state sent along with match snapshots
+    fn state(&self) -> serde_json::Value;
+
+    /// Marks on the board keyed by the notation clients use for their cells
+    fn marks(&self) -> Vec<(String, Mark)> {
+        vec![]
+    }
+
+    /// Makes the choice the last move left pending by picking `cell`, like where a quantum
+    /// cycle collapses. `None` when nothing is pending or `cell` isn't one of the options.
+    fn resolve_pending(&mut self, _cell: Cell) -> Option<Resolution> {
+        None
+    }
+
+    fn box_clone(&self) -> Box<dyn GameRules>;
+}
+
+impl Clone for Box<dyn GameRules> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Rules engine for a new room with the given options
+pub fn rules_for(options: &MatchOptions) -> Box<dyn GameRules> {
+    match options.variant {
+        Variant::Ultimate => Box::<UltimateBoard>::default(),
+        Variant::Notakto => Box::new(NotaktoBoards::new(options.board_count.unwrap_or(1))),
+        Variant::Quantum => Box::<QuantumBoard>::default(),
+        Variant::Standard | Variant::Misere | Variant::Gravity | Variant::Wild => {
+            Box::new(StandardRules::new(options))
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::game_server::board::{Board, Cell, Mark, MoveOutcome};
+use crate::game_server::domain::{MoveInput, TeamSymbol, TurnInput};
+use crate::game_server::rules::{GameRules, Move};
+
+const BOARD_SIZE: usize = 3;
+
+/// Notakto: every move is a cross, a board is dead once it holds three in a row
+#[derive(Debug, Clone)]
+pub struct NotaktoBoards {
+    pub boards: Vec<Board>,
+    pub dead: Vec<bool>,
+}
+
+impl NotaktoBoards {
+    pub fn new(board_count: usize) -> Self {
+        NotaktoBoards {
+            boards: vec![Board::new(BOARD_SIZE, BOARD_SIZE, BOARD_SIZE); board_count],
+            dead: vec![false; board_count],
+        }
+    }
+
+    pub fn is_legal(&self, board: usize, cell: &Cell) -> bool {
+        match (self.boards.get(board), self.dead.get(board)) {
+            (Some(live_board), Some(false)) => {
+                live_board.contains(cell) && !live_board.is_occupied(cell)
+            }
+            _ => false,
+        }
+    }
+
+    /// Plays a move that was checked with [`NotaktoBoards::is_legal`]
+    pub fn place(&mut self, board: usize, cell: Cell, mark: Mark) {
+        self.boards[board].place(cell, mark);
+
+        if self.boards[board].completes_line(&cell) {
+            self.dead[board] = true;
+        }
+    }
+}
+
+impl GameRules for NotaktoBoards {
+    fn reset(&mut self) {
+        *self = NotaktoBoards::new(self.boards.len());
+    }
+
+    fn parse_move(&self, input: &TurnInput, mark: Mark) -> Result<Move, String> {
+        let TurnInput::Coordinates(MoveInput { board, .. }) = input else {
+            return Err("Notakto moves need a row and a column.".into());
+        };
+        let cell = input.to_cell()?;
+
+        // Both players play crosses, and single board matches don't need the board to be named
+        Ok(Move {
+            board: board.unwrap_or_default(),
+            cells: vec![cell],
+            mark: Mark {
+                symbol: TeamSymbol::Cross,
+                ..mark
+            },
+        })
+    }
+
+    fn validate(&self, player_move: &Move) -> bool {
+        let [cell] = player_move.cells.as_slice() else {
+            return false;
+        };
+
+        self.is_legal(player_move.board, cell)
+    }
+
+    fn apply(&mut self, player_move: &Move) -> serde_json::Value {
+        let cell = player_move.cells[0];
+        self.place(player_move.board, cell, player_move.mark);
+
+        serde_json::json!({
+            "board": player_move.board,
+            "row": cell.row,
+            "column": cell.column,
+            "deadBoards": self.dead,
+        })
+    }
+
+    /// Killing the last live board loses
+    fn outcome(&self, _: &Move) -> MoveOutcome {
+        if self.dead.iter().all(|dead| *dead) {
+            MoveOutcome::Defeat
+        } else {
+            MoveOutcome::Continue
+        }
+    }
+
+    /// Occupied cells and liveness of every board
+    fn state(&self) -> serde_json::Value {
+        let boards: Vec<serde_json::Value> = self
+            .boards
+            .iter()
+            .zip(&self.dead)
+            .map(|(board, dead)| {
+                let cells: Vec<String> = board.cells.keys().map(Cell::to_string).collect();
+                serde_json::json!({ "dead": dead, "cells": cells })
+            })
+            .collect();
+
+        serde_json::json!({ "boards": boards })
+    }
+
+    fn box_clone(&self) -> Box<dyn GameRules> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NotaktoBoards;
+    use crate::game_server::board::{Cell, Mark, MoveOutcome};
+    use crate::game_server::domain::TeamSymbol;
+    use crate::game_server::rules::{GameRules, Move};
+    use uuid::Uuid;
+
+    fn cross() -> Mark {
+        Mark {
+            symbol: TeamSymbol::Cross,
+            player_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    pub fn dead_boards_can_not_be_played() {
+        let mut notakto = NotaktoBoards::new(2);
+        for column in 0..3 {
+            notakto.place(0, Cell::new(0, column), cross());
+        }
+
+        assert!(notakto.dead[0]);
+        assert!(!notakto.is_legal(0, &Cell::new(2, 2)));
+        assert!(notakto.is_legal(1, &Cell::new(2, 2)));
+        assert!(!notakto.is_legal(2, &Cell::new(2, 2)));
+    }
+
+    #[test]
+    pub fn killing_the_last_board_loses() {
+        let mut notakto = NotaktoBoards::new(2);
+        let mut outcomes = vec![];
+
+        for board in 0..2 {
+            for row in 0..3 {
+                let player_move = Move {
+                    board,
+                    cells: vec![Cell::new(row, 1)],
+                    mark: cross(),
+                };
+                notakto.apply(&player_move);
+                outcomes.push(notakto.outcome(&player_move));
+            }
+        }
+
+        assert_eq!(outcomes[2], MoveOutcome::Continue);
+        assert_eq!(outcomes[5], MoveOutcome::Defeat);
+    }
+}
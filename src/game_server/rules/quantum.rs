@@ -0,0 +1,472 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::game_server::board::{Board, Cell, Mark, MoveOutcome};
+use crate::game_server::domain::{MoveInput, TurnInput};
+use crate::game_server::rules::{GameRules, Move, Resolution};
+
+const BOARD_SIZE: usize = 3;
+
+/// A move that is still in superposition between two cells
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SpookyMark {
+    pub move_number: usize,
+    pub mark: Mark,
+    pub cells: [Cell; 2],
+}
+
+impl SpookyMark {
+    fn other_cell(&self, cell: &Cell) -> Cell {
+        if self.cells[0] == *cell {
+            self.cells[1]
+        } else {
+            self.cells[0]
+        }
+    }
+}
+
+/// Quantum tic-tac-toe: moves entangle two cells until a cycle forces them to collapse
+#[derive(Debug, Clone)]
+pub struct QuantumBoard {
+    pub classical: Board,
+    pub classical_moves: HashMap<Cell, usize>,
+    pub spooky: Vec<SpookyMark>,
+    /// Move that closed a cycle, the next player has to pick where it collapses before playing
+    pub pending_collapse: Option<usize>,
+    pub move_count: usize,
+}
+
+impl Default for QuantumBoard {
+    fn default() -> Self {
+        QuantumBoard {
+            classical: Board::new(BOARD_SIZE, BOARD_SIZE, BOARD_SIZE),
+            classical_moves: HashMap::new(),
+            spooky: vec![],
+            pending_collapse: None,
+            move_count: 0,
+        }
+    }
+}
+
+impl QuantumBoard {
+    /// Spooky moves take two free cells, the last free cell is played classically instead
+    pub fn is_legal(&self, cells: &[Cell]) -> bool {
+        let all_free = cells
+            .iter()
+            .all(|cell| self.classical.contains(cell) && !self.classical.is_occupied(cell));
+        if self.pending_collapse.is_some() || !all_free {
+            return false;
+        }
+
+        match cells {
+            [_] => self.free_cells() == 1,
+            [first, second] => first != second,
+            _ => false,
+        }
+    }
+
+    fn free_cells(&self) -> usize {
+        BOARD_SIZE * BOARD_SIZE - self.classical.cells.len()
+    }
+
+    /// Plays a move that was checked with [`QuantumBoard::is_legal`], returning whether it closed a cycle
+    pub fn place(&mut self, cells: [Cell; 2], mark: Mark) -> bool {
+        self.move_count += 1;
+        let closes_cycle = self.are_entangled(&cells[0], &cells[1]);
+
+        self.spooky.push(SpookyMark {
+            move_number: self.move_count,
+            mark,
+            cells,
+        });
+
+        if closes_cycle {
+            self.pending_collapse = Some(self.move_count);
+        }
+        closes_cycle
+    }
+
+    /// Plays the last free cell, which takes a classical mark right away
+    pub fn place_classical(&mut self, cell: Cell, mark: Mark) {
+        self.move_count += 1;
+        self.classical.place(cell, mark);
+        self.classical_moves.insert(cell, self.move_count);
+    }
+
+    /// Resolves the pending cycle by putting its last move on `cell`, which decides every
+    /// other move entangled with it. Returns `false` when `cell` is not an option.
+    pub fn collapse_into(&mut self, cell: Cell) -> bool {
+        let Some(move_number) = self.pending_collapse else {
+            return false;
+        };
+        let is_option = self
+            .spooky
+            .iter()
+            .any(|spooky| spooky.move_number == move_number && spooky.cells.contains(&cell));
+        if !is_option {
+            return false;
+        }
+
+        let mut queue = VecDeque::from([(move_number, cell)]);
+        while let Some((move_number, cell)) = queue.pop_front() {
+            let Some(index) = self
+                .spooky
+                .iter()
+                .position(|spooky| spooky.move_number == move_number)
+            else {
+                continue;
+            };
+            let resolved = self.spooky.remove(index);
+
+            if self.classical.is_occupied(&cell) {
+                continue;
+            }
+            self.classical.place(cell, resolved.mark);
+            self.classical_moves.insert(cell, move_number);
+
+            for spooky in self.spooky.iter().filter(|s| s.cells.contains(&cell)) {
+                queue.push_back((spooky.move_number, spooky.other_cell(&cell)));
+            }
+        }
+
+        self.pending_collapse = None;
+        true
+    }
+
+    /// Lines only count once they are classical. When both players have one, the line
+    /// completed with the lower highest move number scores a full point and the other half.
+    /// Without lines the game goes on until the board is full, or until the last free cell
+    /// can't complete a line for anyone.
+    pub fn classical_outcome(&self) -> Resolution {
+        let mut best_lines: Vec<(usize, Mark)> = vec![];
+
+        for line in lines() {
+            let marks: Option<Vec<(&Mark, &usize)>> = line
+                .iter()
+                .map(|cell| {
+                    self.classical
+                        .cells
+                        .get(cell)
+                        .zip(self.classical_moves.get(cell))
+                })
+                .collect();
+            let Some(marks) = marks else {
+                continue;
+            };
+            let (first_mark, _) = marks[0];
+            if marks
+                .iter()
+                .any(|(mark, _)| mark.symbol != first_mark.symbol)
+            {
+                continue;
+            }
+
+            let completed_at = marks.iter().map(|(_, number)| **number).max().unwrap_or(0);
+            match best_lines
+                .iter_mut()
+                .find(|(_, mark)| mark.symbol == first_mark.symbol)
+            {
+                Some(best) if best.0 > completed_at => *best = (completed_at, *first_mark),
+                Some(_) => {}
+                None => best_lines.push((completed_at, *first_mark)),
+            }
+        }
+
+        best_lines.sort_by_key(|(completed_at, _)| *completed_at);
+
+        match best_lines.as_slice() {
+            [] => match self.free_cells() {
+                0 => Resolution::Tied,
+                1 if !self.last_cell_completes_a_line() => Resolution::Tied,
+                _ => Resolution::Undecided,
+            },
+            [(_, winner)] => Resolution::Won {
+                winner: *winner,
+                scores: serde_json::json!({
+                    winner.symbol.to_string(): 1.0,
+                    winner.symbol.next(2).to_string(): 0.0,
+                }),
+            },
+            [(_, winner), (_, runner_up), ..] => Resolution::Won {
+                winner: *winner,
+                scores: serde_json::json!({
+                    winner.symbol.to_string(): 1.0,
+                    runner_up.symbol.to_string(): 0.5,
+                }),
+            },
+        }
+    }
+
+    /// Whether a line has one free cell and two classical marks of the same symbol
+    fn last_cell_completes_a_line(&self) -> bool {
+        lines().iter().any(|line| {
+            let symbols: Vec<_> = line
+                .iter()
+                .filter_map(|cell| self.classical.cells.get(cell))
+                .map(|mark| mark.symbol)
+                .collect();
+            matches!(symbols.as_slice(), [first, second] if first == second)
+        })
+    }
+
+    /// Whether both cells are already connected through spooky marks
+    fn are_entangled(&self, from: &Cell, to: &Cell) -> bool {
+        let mut visited = vec![*from];
+        let mut queue = VecDeque::from([*from]);
+
+        while let Some(cell) = queue.pop_front() {
+            if cell == *to {
+                return true;
+            }
+            for spooky in self.spooky.iter().filter(|s| s.cells.contains(&cell)) {
+                let next = spooky.other_cell(&cell);
+                if !visited.contains(&next) {
+                    visited.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl GameRules for QuantumBoard {
+    fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    fn parse_move(&self, input: &TurnInput, mark: Mark) -> Result<Move, String> {
+        let cells = match input {
+            TurnInput::Coordinates(MoveInput {
+                cells: Some(cells), ..
+            }) => cells.clone(),
+            _ if self.free_cells() == 1 => vec![input.to_cell()?],
+            _ => return Err("Quantum moves need two cells.".into()),
+        };
+
+        Ok(Move {
+            board: 0,
+            cells,
+            mark,
+        })
+    }
+
+    fn validate(&self, player_move: &Move) -> bool {
+        self.is_legal(&player_move.cells)
+    }
+
+    fn apply(&mut self, player_move: &Move) -> serde_json::Value {
+        let collapse_pending = match player_move.cells[..] {
+            [first, second] => self.place([first, second], player_move.mark),
+            [cell] => {
+                self.place_classical(cell, player_move.mark);
+                false
+            }
+            _ => false,
+        };
+
+        serde_json::json!({
+            "moveNumber": self.move_count,
+            "cells": player_move.cells,
+            "collapsePending": collapse_pending,
+        })
+    }
+
+    /// Games are decided once marks collapse, or by the classical move on the last free cell
+    fn outcome(&self, last_move: &Move) -> MoveOutcome {
+        if last_move.cells.len() != 1 {
+            return MoveOutcome::Continue;
+        }
+
+        match self.classical_outcome() {
+            Resolution::Won { winner, .. } if winner.symbol == last_move.mark.symbol => {
+                MoveOutcome::Victory
+            }
+            Resolution::Won { .. } => MoveOutcome::Defeat,
+            Resolution::Tied => MoveOutcome::Tie,
+            Resolution::Undecided => MoveOutcome::Continue,
+        }
+    }
+
+    fn state(&self) -> serde_json::Value {
+        let classical: serde_json::Map<String, serde_json::Value> = self
+            .classical
+            .cells
+            .iter()
+            .map(|(cell, mark)| {
+                let move_number = self.classical_moves.get(cell);
+                (
+                    cell.to_string(),
+                    serde_json::json!({ "symbol": mark.symbol, "moveNumber": move_number }),
+                )
+            })
+            .collect();
+        let spooky: Vec<serde_json::Value> = self
+            .spooky
+            .iter()
+            .map(|spooky| {
+                serde_json::json!({
+                    "moveNumber": spooky.move_number,
+                    "symbol": spooky.mark.symbol,
+                    "cells": spooky.cells,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "classical": classical,
+            "spooky": spooky,
+            "pendingCollapse": self.pending_collapse,
+        })
+    }
+
+    fn resolve_pending(&mut self, cell: Cell) -> Option<Resolution> {
+        self.collapse_into(cell).then(|| self.classical_outcome())
+    }
+
+    fn box_clone(&self) -> Box<dyn GameRules> {
+        Box::new(self.clone())
+    }
+}
+
+fn lines() -> Vec<[Cell; 3]> {
+    let mut lines = vec![];
+    for index in 0..BOARD_SIZE {
+        lines.push([
+            Cell::new(index, 0),
+            Cell::new(index, 1),
+            Cell::new(index, 2),
+        ]);
+        lines.push([
+            Cell::new(0, index),
+            Cell::new(1, index),
+            Cell::new(2, index),
+        ]);
+    }
+    lines.push([Cell::new(0, 0), Cell::new(1, 1), Cell::new(2, 2)]);
+    lines.push([Cell::new(0, 2), Cell::new(1, 1), Cell::new(2, 0)]);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuantumBoard;
+
+    use crate::game_server::board::{Cell, Mark};
+    use crate::game_server::domain::TeamSymbol;
+    use crate::game_server::rules::{GameRules, Move, MoveOutcome, Resolution};
+    use uuid::Uuid;
+
+    fn mark(symbol: TeamSymbol, player_id: Uuid) -> Mark {
+        Mark { symbol, player_id }
+    }
+
+    #[test]
+    pub fn cycles_wait_for_a_collapse() {
+        let mut quantum = QuantumBoard::default();
+        let cross = mark(TeamSymbol::Cross, Uuid::new_v4());
+        let circle = mark(TeamSymbol::Circle, Uuid::new_v4());
+
+        assert!(!quantum.place([Cell::new(0, 0), Cell::new(1, 1)], cross));
+        assert!(!quantum.place([Cell::new(1, 1), Cell::new(2, 2)], circle));
+        assert!(quantum.place([Cell::new(2, 2), Cell::new(0, 0)], cross));
+
+        assert_eq!(quantum.pending_collapse, Some(3));
+        assert!(!quantum.is_legal(&[Cell::new(0, 1), Cell::new(0, 2)]));
+        assert!(!quantum.collapse_into(Cell::new(1, 1)));
+    }
+
+    #[test]
+    pub fn collapse_resolves_every_entangled_move() {
+        let mut quantum = QuantumBoard::default();
+        let cross = mark(TeamSymbol::Cross, Uuid::new_v4());
+        let circle = mark(TeamSymbol::Circle, Uuid::new_v4());
+
+        quantum.place([Cell::new(0, 0), Cell::new(1, 1)], cross);
+        quantum.place([Cell::new(1, 1), Cell::new(2, 2)], circle);
+        quantum.place([Cell::new(2, 2), Cell::new(0, 0)], cross);
+        quantum.place([Cell::new(0, 1), Cell::new(0, 2)], circle);
+
+        assert!(quantum.collapse_into(Cell::new(0, 0)));
+
+        assert_eq!(quantum.classical_moves.get(&Cell::new(0, 0)), Some(&3));
+        assert_eq!(quantum.classical_moves.get(&Cell::new(1, 1)), Some(&1));
+        assert_eq!(quantum.classical_moves.get(&Cell::new(2, 2)), Some(&2));
+        assert_eq!(
+            quantum.spooky.len(),
+            1,
+            "Moves outside of the cycle stay spooky"
+        );
+        assert_eq!(quantum.pending_collapse, None);
+    }
+
+    #[test]
+    pub fn simultaneous_lines_favor_the_earlier_one() {
+        let mut quantum = QuantumBoard::default();
+        let cross_id = Uuid::new_v4();
+        let circle_id = Uuid::new_v4();
+        let cross = mark(TeamSymbol::Cross, cross_id);
+        let circle = mark(TeamSymbol::Circle, circle_id);
+
+        for (row, symbol_mark, move_number) in [
+            (0, cross, 1),
+            (0, cross, 5),
+            (0, cross, 3),
+            (2, circle, 2),
+            (2, circle, 4),
+            (2, circle, 6),
+        ] {
+            let column = (move_number - 1) / 2;
+            quantum.classical.place(Cell::new(row, column), symbol_mark);
+            quantum
+                .classical_moves
+                .insert(Cell::new(row, column), move_number);
+        }
+
+        let Resolution::Won { winner, scores } = quantum.classical_outcome() else {
+            panic!("Expected a winner");
+        };
+        assert_eq!(winner.player_id, cross_id);
+        assert_eq!(scores, serde_json::json!({ "Cross": 1.0, "Circle": 0.5 }));
+    }
+
+    #[test]
+    pub fn last_free_cell_is_played_classically() {
+        let mut quantum = QuantumBoard::default();
+        let cross = mark(TeamSymbol::Cross, Uuid::new_v4());
+        let circle = mark(TeamSymbol::Circle, Uuid::new_v4());
+
+        for (move_number, (row, column, symbol_mark)) in [
+            (0, 0, cross),
+            (1, 0, circle),
+            (0, 1, cross),
+            (1, 1, circle),
+            (1, 2, cross),
+            (2, 1, circle),
+            (2, 0, cross),
+            (2, 2, circle),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            quantum.classical.place(Cell::new(row, column), symbol_mark);
+            quantum
+                .classical_moves
+                .insert(Cell::new(row, column), move_number + 1);
+        }
+        quantum.move_count = 8;
+
+        assert_eq!(quantum.classical_outcome(), Resolution::Undecided);
+        assert!(!quantum.is_legal(&[Cell::new(0, 2), Cell::new(0, 2)]));
+        assert!(quantum.is_legal(&[Cell::new(0, 2)]));
+
+        let last_move = Move {
+            board: 0,
+            cells: vec![Cell::new(0, 2)],
+            mark: cross,
+        };
+        quantum.apply(&last_move);
+
+        assert_eq!(quantum.outcome(&last_move), MoveOutcome::Victory);
+    }
+}
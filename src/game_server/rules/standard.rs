@@ -0,0 +1,278 @@
+use crate::game_server::board::{Board, Cell, Mark, MoveOutcome};
+use crate::game_server::domain::{
+    MatchOptions, MoveInput, MoveNotation, TeamSymbol, TurnInput, TurnMove, Variant,
+};
+use crate::game_server::rules::{GameRules, Move};
+
+/// Tic-tac-toe on a single m,n,k board, along with the variants that only tweak how
+/// moves are placed (gravity, wild) or how a completed line is scored (misère)
+#[derive(Debug, Clone)]
+pub struct StandardRules {
+    pub board: Board,
+    pub variant: Variant,
+}
+
+impl StandardRules {
+    pub fn new(options: &MatchOptions) -> Self {
+        StandardRules {
+            board: Board::new(
+                options.board_width(),
+                options.board_height(),
+                options.win_length(),
+            ),
+            variant: options.variant,
+        }
+    }
+
+    /// Whether moves are exchanged as the legacy 3x3 codes instead of coordinates
+    pub fn uses_move_codes(&self) -> bool {
+        self.board.is_classic() && matches!(self.variant, Variant::Standard | Variant::Misere)
+    }
+
+    fn classic_move(&self, cell: &Cell) -> Option<TurnMove> {
+        TurnMove::from_cell(cell).filter(|_| self.uses_move_codes())
+    }
+
+    /// How a move on this board is shown to clients, classic boards keep the 3x3 notations
+    pub fn move_notation(&self, cell: &Cell, notation: MoveNotation) -> serde_json::Value {
+        match self.classic_move(cell) {
+            Some(turn_move) => serde_json::json!(turn_move.notation(notation)),
+            None => serde_json::json!(cell),
+        }
+    }
+
+    /// Key used for `cell` in board snapshots, mirroring [`StandardRules::move_notation`]
+    pub fn cell_key(&self, cell: &Cell) -> String {
+        match self.classic_move(cell) {
+            Some(turn_move) => turn_move.to_string(),
+            None => cell.to_string(),
+        }
+    }
+
+    /// 3x3 notations only make sense on the classic board, anything else must use coordinates
+    fn parse_cell(&self, input: &TurnInput) -> Result<Cell, String> {
+        if matches!(input, TurnInput::Code(_)) && !self.board.is_classic() {
+            return Err("Moves on this board need a row and a column.".into());
+        }
+
+        input.to_cell()
+    }
+
+    /// Gravity moves only pick a column, the mark falls to the lowest empty row of it
+    fn drop_cell(&self, input: &TurnInput) -> Result<Cell, String> {
+        let TurnInput::Coordinates(MoveInput {
+            column: Some(column),
+            ..
+        }) = input
+        else {
+            return Err("Gravity moves need a column.".into());
+        };
+
+        self.board
+            .lowest_empty_row(*column)
+            .map(|row| Cell::new(row, *column))
+            .ok_or_else(|| "Column is full or off the board.".into())
+    }
+}
+
+impl GameRules for StandardRules {
+    fn reset(&mut self) {
+        self.board.clear();
+    }
+
+    fn parse_move(&self, input: &TurnInput, mark: Mark) -> Result<Move, String> {
+        let cell = if self.variant == Variant::Gravity {
+            self.drop_cell(input)
+        } else {
+            self.parse_cell(input)
+        }?;
+
+        let mark = if self.variant == Variant::Wild {
+            let TurnInput::Coordinates(MoveInput {
+                symbol: Some(symbol),
+                ..
+            }) = input
+            else {
+                return Err("Wild moves need a symbol.".into());
+            };
+            if !matches!(symbol, TeamSymbol::Cross | TeamSymbol::Circle) {
+                return Err("Wild moves can only place Cross or Circle.".into());
+            }
+            Mark {
+                symbol: *symbol,
+                ..mark
+            }
+        } else {
+            mark
+        };
+
+        Ok(Move {
+            board: 0,
+            cells: vec![cell],
+            mark,
+        })
+    }
+
+    fn validate(&self, player_move: &Move) -> bool {
+        let [cell] = player_move.cells.as_slice() else {
+            return false;
+        };
+
+        if self.variant == Variant::Gravity
+            && self.board.lowest_empty_row(cell.column) != Some(cell.row)
+        {
+            return false;
+        }
+
+        self.board.contains(cell) && !self.board.is_occupied(cell)
+    }
+
+    fn apply(&mut self, player_move: &Move) -> serde_json::Value {
+        let cell = player_move.cells[0];
+        self.board.place(cell, player_move.mark);
+
+        let mut body = self.move_notation(&cell, MoveNotation::Code);
+        if self.variant == Variant::Wild {
+            body["symbol"] = serde_json::json!(player_move.mark.symbol);
+        }
+        body
+    }
+
+    fn outcome(&self, last_move: &Move) -> MoveOutcome {
+        if self.board.completes_line(&last_move.cells[0]) {
+            // In misère the player completing a line is the one who loses
+            if self.variant == Variant::Misere {
+                MoveOutcome::Defeat
+            } else {
+                MoveOutcome::Victory
+            }
+        } else if self.board.is_full() {
+            MoveOutcome::Tie
+        } else {
+            MoveOutcome::Continue
+        }
+    }
+
+    fn notated_move(
+        &self,
+        player_move: &Move,
+        notation: MoveNotation,
+    ) -> Option<serde_json::Value> {
+        let cell = player_move.cells[0];
+        self.classic_move(&cell)
+            .map(|_| self.move_notation(&cell, notation))
+    }
+
+    fn state(&self) -> serde_json::Value {
+        serde_json::json!({
+            "width": self.board.width,
+            "height": self.board.height,
+            "winLength": self.board.win_length,
+        })
+    }
+
+    fn marks(&self) -> Vec<(String, Mark)> {
+        self.board
+            .cells
+            .iter()
+            .map(|(cell, mark)| (self.cell_key(cell), *mark))
+            .collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn GameRules> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StandardRules;
+    use crate::game_server::board::{Cell, Mark, MoveOutcome};
+    use crate::game_server::domain::{MatchOptions, MoveInput, TeamSymbol, TurnInput, Variant};
+    use crate::game_server::rules::GameRules;
+    use uuid::Uuid;
+
+    fn cross() -> Mark {
+        Mark {
+            symbol: TeamSymbol::Cross,
+            player_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    pub fn legacy_codes_are_only_read_on_classic_boards() {
+        let classic = StandardRules::new(&MatchOptions::default());
+        let larger = StandardRules::new(&MatchOptions {
+            board_size: 4,
+            ..Default::default()
+        });
+        let code = TurnInput::Code("UL".into());
+
+        assert!(classic.parse_move(&code, cross()).is_ok());
+        assert!(larger.parse_move(&code, cross()).is_err());
+    }
+
+    #[test]
+    pub fn gravity_moves_resolve_their_row() {
+        let mut rules = StandardRules::new(&MatchOptions {
+            variant: Variant::Gravity,
+            board_size: 7,
+            board_height: Some(6),
+            ..Default::default()
+        });
+        let column = TurnInput::Coordinates(MoveInput {
+            column: Some(2),
+            ..Default::default()
+        });
+
+        let first = rules.parse_move(&column, cross()).unwrap();
+        rules.apply(&first);
+        let second = rules.parse_move(&column, cross()).unwrap();
+
+        assert_eq!(first.cells, vec![Cell::new(5, 2)]);
+        assert_eq!(second.cells, vec![Cell::new(4, 2)]);
+    }
+
+    #[test]
+    pub fn wild_moves_only_place_cross_or_circle() {
+        let rules = StandardRules::new(&MatchOptions {
+            variant: Variant::Wild,
+            ..Default::default()
+        });
+        let wild_move = |symbol| {
+            TurnInput::Coordinates(MoveInput {
+                row: Some(0),
+                column: Some(0),
+                symbol: Some(symbol),
+                ..Default::default()
+            })
+        };
+
+        assert!(rules
+            .parse_move(&wild_move(TeamSymbol::Circle), cross())
+            .is_ok());
+        assert!(rules
+            .parse_move(&wild_move(TeamSymbol::Triangle), cross())
+            .is_err());
+    }
+
+    #[test]
+    pub fn misere_lines_lose() {
+        let mut rules = StandardRules::new(&MatchOptions {
+            variant: Variant::Misere,
+            ..Default::default()
+        });
+        let mark = cross();
+        let mut outcome = MoveOutcome::Continue;
+
+        for code in ["UL", "UM", "UR"] {
+            let player_move = rules
+                .parse_move(&TurnInput::Code(code.into()), mark)
+                .unwrap();
+            rules.apply(&player_move);
+            outcome = rules.outcome(&player_move);
+        }
+
+        assert_eq!(outcome, MoveOutcome::Defeat);
+    }
+}
@@ -0,0 +1,211 @@
+use serde::Serialize;
+
+use crate::game_server::board::{Board, Cell, Mark, MoveOutcome};
+use crate::game_server::domain::{MoveInput, TeamSymbol, TurnInput};
+use crate::game_server::rules::{GameRules, Move};
+
+const GRID_SIZE: usize = 3;
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize)]
+pub enum SubBoardStatus {
+    Open,
+    Won(TeamSymbol),
+    Tied,
+}
+
+/// Ultimate tic-tac-toe: a 3x3 grid of 3x3 boards, sub-boards are numbered row by row
+#[derive(Debug, Clone)]
+pub struct UltimateBoard {
+    pub boards: Vec<Board>,
+    pub statuses: Vec<SubBoardStatus>,
+    /// Holds the winner of every won sub-board, lines on it decide the match
+    pub meta: Board,
+    /// Sub-board the next move must be played on, any open one when `None`
+    pub next_board: Option<usize>,
+}
+
+impl Default for UltimateBoard {
+    fn default() -> Self {
+        let board_count = GRID_SIZE * GRID_SIZE;
+
+        UltimateBoard {
+            boards: vec![Board::new(GRID_SIZE, GRID_SIZE, GRID_SIZE); board_count],
+            statuses: vec![SubBoardStatus::Open; board_count],
+            meta: Board::new(GRID_SIZE, GRID_SIZE, GRID_SIZE),
+            next_board: None,
+        }
+    }
+}
+
+impl UltimateBoard {
+    pub fn is_legal(&self, board: usize, cell: &Cell) -> bool {
+        if self.next_board.is_some_and(|next| next != board) {
+            return false;
+        }
+
+        match (self.boards.get(board), self.statuses.get(board)) {
+            (Some(sub_board), Some(SubBoardStatus::Open)) => {
+                sub_board.contains(cell) && !sub_board.is_occupied(cell)
+            }
+            _ => false,
+        }
+    }
+
+    /// Plays a move that was checked with [`UltimateBoard::is_legal`]
+    pub fn place(&mut self, board: usize, cell: Cell, mark: Mark) {
+        let sub_board = &mut self.boards[board];
+        sub_board.place(cell, mark);
+
+        if sub_board.completes_line(&cell) {
+            self.statuses[board] = SubBoardStatus::Won(mark.symbol);
+            self.meta.place(meta_cell(board), mark);
+        } else if sub_board.is_full() {
+            self.statuses[board] = SubBoardStatus::Tied;
+        }
+
+        let next_board = cell.row * GRID_SIZE + cell.column;
+        self.next_board = (self.statuses[next_board] == SubBoardStatus::Open).then_some(next_board);
+    }
+
+    /// Outcome once a move was played on `board`
+    pub fn outcome_after(&self, board: usize) -> MoveOutcome {
+        if matches!(self.statuses[board], SubBoardStatus::Won(_))
+            && self.meta.completes_line(&meta_cell(board))
+        {
+            MoveOutcome::Victory
+        } else if self.statuses.contains(&SubBoardStatus::Open) {
+            MoveOutcome::Continue
+        } else {
+            MoveOutcome::Tie
+        }
+    }
+}
+
+/// Cell of the meta board standing for sub-board `board`
+fn meta_cell(board: usize) -> Cell {
+    Cell::new(board / GRID_SIZE, board % GRID_SIZE)
+}
+
+impl GameRules for UltimateBoard {
+    fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    fn parse_move(&self, input: &TurnInput, mark: Mark) -> Result<Move, String> {
+        let TurnInput::Coordinates(MoveInput {
+            board: Some(board), ..
+        }) = input
+        else {
+            return Err("Ultimate moves need a board, a row and a column.".into());
+        };
+        let cell = input.to_cell()?;
+
+        Ok(Move {
+            board: *board,
+            cells: vec![cell],
+            mark,
+        })
+    }
+
+    fn validate(&self, player_move: &Move) -> bool {
+        let [cell] = player_move.cells.as_slice() else {
+            return false;
+        };
+
+        self.is_legal(player_move.board, cell)
+    }
+
+    fn apply(&mut self, player_move: &Move) -> serde_json::Value {
+        let cell = player_move.cells[0];
+        self.place(player_move.board, cell, player_move.mark);
+
+        serde_json::json!({
+            "board": player_move.board,
+            "row": cell.row,
+            "column": cell.column,
+            "nextBoard": self.next_board,
+            "boards": self.statuses,
+        })
+    }
+
+    fn outcome(&self, last_move: &Move) -> MoveOutcome {
+        self.outcome_after(last_move.board)
+    }
+
+    /// Status of every sub-board and where the next move has to go
+    fn state(&self) -> serde_json::Value {
+        let boards: Vec<serde_json::Value> = self
+            .boards
+            .iter()
+            .zip(&self.statuses)
+            .map(|(board, status)| {
+                let cells: serde_json::Map<String, serde_json::Value> = board
+                    .cells
+                    .iter()
+                    .map(|(cell, mark)| (cell.to_string(), serde_json::json!(mark.symbol)))
+                    .collect();
+                serde_json::json!({ "status": status, "cells": cells })
+            })
+            .collect();
+
+        serde_json::json!({ "boards": boards, "nextBoard": self.next_board })
+    }
+
+    fn box_clone(&self) -> Box<dyn GameRules> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SubBoardStatus, UltimateBoard};
+    use crate::game_server::board::{Cell, Mark, MoveOutcome};
+    use crate::game_server::domain::TeamSymbol;
+    use uuid::Uuid;
+
+    fn mark(symbol: TeamSymbol) -> Mark {
+        Mark {
+            symbol,
+            player_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    pub fn cell_played_decides_next_board() {
+        let mut ultimate = UltimateBoard::default();
+
+        ultimate.place(4, Cell::new(0, 2), mark(TeamSymbol::Cross));
+
+        assert_eq!(ultimate.next_board, Some(2));
+        assert!(ultimate.is_legal(2, &Cell::new(1, 1)));
+        assert!(!ultimate.is_legal(4, &Cell::new(1, 1)));
+    }
+
+    #[test]
+    pub fn closed_boards_free_the_next_move() {
+        let mut ultimate = UltimateBoard::default();
+        for column in 0..3 {
+            ultimate.place(0, Cell::new(0, column), mark(TeamSymbol::Cross));
+        }
+
+        assert_eq!(ultimate.statuses[0], SubBoardStatus::Won(TeamSymbol::Cross));
+
+        ultimate.place(8, Cell::new(0, 0), mark(TeamSymbol::Circle));
+
+        assert_eq!(ultimate.next_board, None);
+        assert!(!ultimate.is_legal(0, &Cell::new(2, 2)));
+        assert!(ultimate.is_legal(5, &Cell::new(2, 2)));
+    }
+
+    #[test]
+    pub fn line_of_won_boards_wins_the_match() {
+        let mut ultimate = UltimateBoard::default();
+        for board in [0, 4, 8] {
+            for column in 0..3 {
+                ultimate.place(board, Cell::new(1, column), mark(TeamSymbol::Cross));
+            }
+        }
+
+        assert_eq!(ultimate.outcome_after(8), MoveOutcome::Victory);
+    }
+}
@@ -1,43 +1,269 @@
 use actix::dev::{MessageResponse, OneshotSender};
-use actix::prelude::{Actor, Context, Message, Recipient};
-use serde::Serialize;
+use actix::prelude::{Actor, AsyncContext, Context, Message, Recipient, SpawnHandle};
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     sync::{atomic::AtomicUsize, Arc},
+    time::Instant,
 };
 use uuid::Uuid;
 
-use super::domain::TurnMove;
-use crate::game_server::domain::TeamSymbol;
+use super::commands::{
+    OutboundEvent, PlayerPresence, Presence, RoomRemoved, RoomSummary, SeatedPlayer,
+};
+use super::events::close_idle_rooms;
+use super::record::{record_date, GameRecord, GameResult, RecordedMove};
+use super::rules::{rules_for, GameRules};
+use super::stats::{PlayerStats, StatsSummary};
+use crate::configuration::RoomCleanupSettings;
+use crate::game_server::domain::{
+    MatchCursor, MatchListQuery, MatchOptions, MoveNotation, PresenceState, RoomReference,
+    TeamSymbol,
+};
 
 #[derive(Message)]
 #[rtype(result = "()")]
-pub struct ServerMessage(pub String);
+pub struct ServerMessage(pub OutboundEvent);
 
 #[derive(Debug)]
 pub struct GameServer {
     pub sessions: HashMap<Uuid, Recipient<ServerMessage>>,
+    pub usernames: HashMap<Uuid, String>,
+    /// Sessions that entered the lobby, they only take part in it while they are in no room
+    pub lobby_members: HashSet<Uuid>,
+    pub room_subscribers: HashSet<Uuid>,
+    pub rooms_created: u64,
+    /// Rooms closed for sitting idle too long
+    pub rooms_reaped: u64,
+    pub room_cleanup: RoomCleanupSettings,
     pub rooms: HashMap<Uuid, GameRoom>,
+    /// Room each session is playing or spectating in, a session is in at most one room
+    pub player_rooms: HashMap<Uuid, Uuid>,
     pub visitor_count: Arc<AtomicUsize>,
+    pub chat_history: HashMap<Uuid, VecDeque<Instant>>,
+    pub muted_players: HashMap<Uuid, HashSet<Uuid>>,
+    /// Notation each session wants moves written in, sessions not listed get the legacy codes
+    pub move_notations: HashMap<Uuid, MoveNotation>,
+    /// Records of finished games, oldest ones are dropped past [`MAX_GAME_RECORDS`] games
+    /// played here or [`MAX_IMPORTED_RECORDS`] imported ones
+    pub game_records: HashMap<Uuid, GameRecord>,
+    pub game_record_order: VecDeque<Uuid>,
+    pub imported_record_order: VecDeque<Uuid>,
+    /// Replays being streamed to each session
+    pub replays: HashMap<Uuid, Replay>,
+    /// Aggregates of every known player who finished or forfeited a game, kept after they
+    /// disconnect so they carry over when the player reconnects
+    pub player_stats: HashMap<Uuid, PlayerStats>,
+    /// Player id each reconnect token stands for, the least recently connected players are
+    /// forgotten past [`MAX_KNOWN_PLAYERS`]
+    pub player_tokens: HashMap<Uuid, Uuid>,
+    pub player_token_order: VecDeque<Uuid>,
+}
+
+pub const MAX_GAME_RECORDS: usize = 1000;
+pub const MAX_IMPORTED_RECORDS: usize = 100;
+pub const MAX_KNOWN_PLAYERS: usize = 10_000;
+
+/// A recorded game being streamed back to a session
+#[derive(Debug)]
+pub struct Replay {
+    pub game_id: Uuid,
+    /// Message sent for each move of the game, in order
+    pub steps: Vec<OutboundEvent>,
+    /// Number of moves already sent
+    pub position: usize,
+    pub speed: f64,
+    pub paused: bool,
+    /// Timer sending the next move while the replay plays
+    pub timer: Option<SpawnHandle>,
 }
 
 #[derive(Debug, Clone)]
 pub struct GameRoom {
     pub players: HashMap<Uuid, String>,
+    pub spectators: HashMap<Uuid, String>,
     pub status: GameRoomStatus,
     pub current_turn: TeamSymbol,
     pub name: String,
-    pub moves_made: HashMap<TurnMove, Uuid>,
+    /// Rules of the room's variant along with its board
+    pub rules: Box<dyn GameRules>,
+    pub options: MatchOptions,
+    pub invite_code: String,
+    /// Order in which the room was created, used to keep listings stable
+    pub sequence: u64,
+    /// Moves played in the current game, kept for its record
+    pub history: Vec<RecordedMove>,
+    /// Players in seat order, the first one plays [`TeamSymbol::for_seat`] 0 and so on
+    pub seats: Vec<Uuid>,
+    /// When the current turn started, used to time moves
+    pub turn_started: Instant,
+    /// Last time a player entered, left or moved, idle rooms are closed after a while
+    pub last_activity: Instant,
 }
 
 impl GameRoom {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, options: MatchOptions, invite_code: String, sequence: u64) -> Self {
         GameRoom {
             players: HashMap::new(),
+            spectators: HashMap::new(),
             status: GameRoomStatus::Waiting,
             current_turn: TeamSymbol::Cross,
             name,
-            moves_made: HashMap::new(),
+            rules: rules_for(&options),
+            options,
+            invite_code,
+            sequence,
+            history: vec![],
+            seats: vec![],
+            turn_started: Instant::now(),
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// Public view of the room as shown in match listings
+    pub fn summary(&self, room_id: &Uuid) -> RoomSummary {
+        RoomSummary {
+            match_id: *room_id,
+            room_name: self.name.clone(),
+            players: format!("{}/{}", self.players.len(), self.options.player_count),
+            spectators: self.spectators.len(),
+            status: self.status.clone(),
+            password_protected: self.options.is_password_protected(),
+            options: self.options.clone(),
+        }
+    }
+
+    pub fn cursor(&self) -> MatchCursor {
+        MatchCursor {
+            sequence: self.sequence,
+            name: self.name.clone(),
+        }
+    }
+
+    pub fn matches_query(&self, query: &MatchListQuery) -> bool {
+        if query.status.as_ref().is_some_and(|s| *s != self.status) {
+            return false;
+        }
+        if query.joinable_only && (self.status != GameRoomStatus::Waiting || self.is_full()) {
+            return false;
+        }
+        if query.variant.is_some_and(|v| v != self.options.variant) {
+            return false;
+        }
+        if query.rated.is_some_and(|r| r != self.options.rated) {
+            return false;
+        }
+        if let Some(search) = &query.search {
+            if !self.name.to_lowercase().contains(&search.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.players.len() >= self.options.player_count
+    }
+
+    /// Whether the room may be listed or announced to sessions outside of it
+    pub fn is_public(&self) -> bool {
+        !self.options.private
+    }
+
+    /// Seats a player in the first free seat, returning the symbol they play
+    pub fn add_player(&mut self, player_id: Uuid, username: String) -> TeamSymbol {
+        self.players.insert(player_id, username);
+        self.seats.push(player_id);
+        self.last_activity = Instant::now();
+
+        TeamSymbol::for_seat(self.seats.len() - 1)
+    }
+
+    /// Frees the player's seat, the players seated after them move up one seat so the
+    /// remaining seats always start at Cross
+    pub fn remove_player(&mut self, player_id: &Uuid) {
+        self.players.remove(player_id);
+        self.seats.retain(|id| id != player_id);
+        self.last_activity = Instant::now();
+    }
+
+    /// Why the room should be closed, if it has been idle longer than its status allows
+    pub fn idle_reason(&self, settings: &RoomCleanupSettings) -> Option<&'static str> {
+        let (timeout, reason) = match self.status {
+            GameRoomStatus::Waiting => (
+                settings.waiting_room_timeout_seconds,
+                "The match was not started in time.",
+            ),
+            GameRoomStatus::Started => (
+                settings.started_room_timeout_seconds,
+                "The game was abandoned.",
+            ),
+            GameRoomStatus::Finished => {
+                (settings.finished_room_timeout_seconds, "The game is over.")
+            }
+        };
+
+        (self.last_activity.elapsed().as_secs() >= timeout).then_some(reason)
+    }
+
+    /// Symbol of the seat `player_id` has in the room
+    pub fn symbol_of(&self, player_id: &Uuid) -> Option<TeamSymbol> {
+        self.seats
+            .iter()
+            .position(|id| id == player_id)
+            .map(TeamSymbol::for_seat)
+    }
+
+    /// Players in seat order along with the symbol each of them plays
+    pub fn seated_players(&self) -> Vec<SeatedPlayer> {
+        self.seats
+            .iter()
+            .enumerate()
+            .map(|(seat, id)| SeatedPlayer {
+                player_id: *id,
+                username: self.players.get(id).cloned().unwrap_or_default(),
+                symbol: TeamSymbol::for_seat(seat),
+            })
+            .collect()
+    }
+
+    pub fn player_in_seat(&self, symbol: TeamSymbol) -> Option<&Uuid> {
+        self.seats
+            .iter()
+            .find(|id| self.symbol_of(id) == Some(symbol))
+    }
+
+    /// Record of the game just finished in the room
+    pub fn game_record(&self, result: GameResult) -> GameRecord {
+        let players = (0..self.options.player_count)
+            .map(TeamSymbol::for_seat)
+            .map(|symbol| {
+                let username = self
+                    .player_in_seat(symbol)
+                    .and_then(|id| self.players.get(id))
+                    .cloned()
+                    .unwrap_or_else(|| "?".into());
+                (symbol, username)
+            })
+            .collect();
+
+        GameRecord {
+            event: self.name.clone(),
+            date: record_date(),
+            players,
+            options: MatchOptions {
+                password: None,
+                ..self.options.clone()
+            },
+            result,
+            moves: self.history.clone(),
+        }
+    }
+
+    pub fn check_password(&self, password: Option<&str>) -> bool {
+        match &self.options.password {
+            Some(room_password) => password == Some(room_password.as_str()),
+            None => true,
         }
     }
 }
@@ -57,7 +283,7 @@ where
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum GameRoomStatus {
     Waiting,
     Started,
@@ -65,25 +291,42 @@ pub enum GameRoomStatus {
 }
 
 impl GameServer {
-    pub fn new(visitor_count: Arc<AtomicUsize>) -> GameServer {
+    pub fn new(visitor_count: Arc<AtomicUsize>, room_cleanup: RoomCleanupSettings) -> GameServer {
         let rooms = HashMap::new();
 
         GameServer {
             sessions: HashMap::new(),
+            usernames: HashMap::new(),
+            lobby_members: HashSet::new(),
+            room_subscribers: HashSet::new(),
+            rooms_created: 0,
+            rooms_reaped: 0,
+            room_cleanup,
             rooms,
+            player_rooms: HashMap::new(),
             visitor_count,
+            chat_history: HashMap::new(),
+            muted_players: HashMap::new(),
+            move_notations: HashMap::new(),
+            game_records: HashMap::new(),
+            game_record_order: VecDeque::new(),
+            imported_record_order: VecDeque::new(),
+            replays: HashMap::new(),
+            player_stats: HashMap::new(),
+            player_tokens: HashMap::new(),
+            player_token_order: VecDeque::new(),
         }
     }
 }
 
 impl GameServer {
     /// Relay message to everyone else in the room
-    pub fn send_message(&self, room: &Uuid, message: &str, skip_id: Uuid) {
+    pub fn send_message(&self, room: &Uuid, message: &OutboundEvent, skip_id: Uuid) {
         if let Some(game_room) = self.rooms.get(room) {
             for (id, _) in game_room.players.iter() {
                 if *id != skip_id {
                     if let Some(addr) = self.sessions.get(id) {
-                        addr.do_send(ServerMessage(message.to_owned()));
+                        addr.do_send(ServerMessage(message.clone()));
                     }
                 }
             }
@@ -91,22 +334,282 @@ impl GameServer {
     }
 
     /// Send message to all users in the room
-    pub fn send_message_all(&self, room: &Uuid, message: &str) {
+    pub fn send_message_all(&self, room: &Uuid, message: &OutboundEvent) {
         if let Some(game_room) = self.rooms.get(room) {
             for (id, _) in game_room.players.iter() {
                 if let Some(addr) = self.sessions.get(id) {
-                    addr.do_send(ServerMessage(message.to_owned()));
+                    addr.do_send(ServerMessage(message.clone()));
+                }
+            }
+        }
+    }
+
+    /// Send message to everyone spectating the room
+    pub fn send_message_spectators(&self, room: &Uuid, message: &OutboundEvent) {
+        if let Some(game_room) = self.rooms.get(room) {
+            for (id, _) in game_room.spectators.iter() {
+                if let Some(addr) = self.sessions.get(id) {
+                    addr.do_send(ServerMessage(message.clone()));
+                }
+            }
+        }
+    }
+
+    /// Send chat message to everyone in the room who hasn't muted the sender
+    pub fn send_chat_message(&self, room: &Uuid, message: &OutboundEvent, sender_id: Uuid) {
+        if let Some(game_room) = self.rooms.get(room) {
+            for id in game_room.players.keys().chain(game_room.spectators.keys()) {
+                if self.is_muted_by(&sender_id, id) {
+                    continue;
+                }
+                if let Some(addr) = self.sessions.get(id) {
+                    addr.do_send(ServerMessage(message.clone()));
                 }
             }
         }
     }
 
+    /// Whether the session entered the lobby and isn't playing or spectating in a room
+    pub fn is_in_lobby(&self, player_id: &Uuid) -> bool {
+        self.lobby_members.contains(player_id) && !self.player_rooms.contains_key(player_id)
+    }
+
+    fn lobby(&self) -> impl Iterator<Item = &Uuid> {
+        self.lobby_members
+            .iter()
+            .filter(|id| !self.player_rooms.contains_key(id))
+    }
+
+    /// Send message to everyone in the lobby who hasn't muted the sender
+    pub fn send_lobby_message(&self, message: &OutboundEvent, sender_id: Uuid) {
+        for id in self.lobby() {
+            if self.is_muted_by(&sender_id, id) {
+                continue;
+            }
+            if let Some(addr) = self.sessions.get(id) {
+                addr.do_send(ServerMessage(message.clone()));
+            }
+        }
+    }
+
+    /// Push the presence list of every connected player to everyone in the lobby
+    pub fn broadcast_presence(&self) {
+        if self.lobby().next().is_none() {
+            return;
+        }
+
+        let players = self
+            .sessions
+            .keys()
+            .map(|id| PlayerPresence {
+                player_id: *id,
+                username: self.usernames.get(id).cloned().unwrap_or_default(),
+                state: self.presence_state(id),
+            })
+            .collect();
+
+        let command = OutboundEvent::Presence(Presence { players });
+
+        for id in self.lobby() {
+            if let Some(addr) = self.sessions.get(id) {
+                self.send_direct_message(addr, &command);
+            }
+        }
+    }
+
+    pub fn find_room_id(&self, room: &RoomReference) -> Option<Uuid> {
+        match room {
+            RoomReference::Id(id) => self.rooms.contains_key(id).then_some(*id),
+            RoomReference::InviteCode(code) => self
+                .rooms
+                .iter()
+                .find(|(_, r)| r.invite_code == *code)
+                .map(|(id, _)| *id),
+        }
+    }
+
+    /// Stats of a player as sent to clients, `None` for unknown players
+    pub fn player_stats_summary(&self, player_id: &Uuid) -> Option<StatsSummary> {
+        match self.player_stats.get(player_id) {
+            Some(stats) => Some(stats.summary(player_id)),
+            None if self.sessions.contains_key(player_id) => {
+                Some(PlayerStats::default().summary(player_id))
+            }
+            None => None,
+        }
+    }
+
+    /// Remembers which player a reconnect token stands for, forgetting the least recently
+    /// connected players along with their stats once there are too many
+    pub fn remember_player(&mut self, token: Uuid, player_id: Uuid) {
+        self.player_token_order.retain(|known| *known != token);
+        self.player_token_order.push_back(token);
+        self.player_tokens.insert(token, player_id);
+
+        while self.player_token_order.len() > MAX_KNOWN_PLAYERS {
+            if let Some(oldest) = self.player_token_order.pop_front() {
+                if let Some(player_id) = self.player_tokens.remove(&oldest) {
+                    self.player_stats.remove(&player_id);
+                }
+            }
+        }
+    }
+
+    /// Keeps a finished game's record, returning the id it can be downloaded with
+    pub fn store_game_record(&mut self, record: GameRecord) -> Uuid {
+        self.keep_game_record(record, false)
+    }
+
+    /// Keeps an imported record apart from played games, so imports can't push them out
+    pub fn store_imported_record(&mut self, record: GameRecord) -> Uuid {
+        self.keep_game_record(record, true)
+    }
+
+    fn keep_game_record(&mut self, record: GameRecord, imported: bool) -> Uuid {
+        let (order, limit) = if imported {
+            (&mut self.imported_record_order, MAX_IMPORTED_RECORDS)
+        } else {
+            (&mut self.game_record_order, MAX_GAME_RECORDS)
+        };
+        let game_id = Uuid::new_v4();
+        self.game_records.insert(game_id, record);
+        order.push_back(game_id);
+
+        while order.len() > limit {
+            if let Some(oldest) = order.pop_front() {
+                self.game_records.remove(&oldest);
+            }
+        }
+
+        game_id
+    }
+
+    /// Tell lobby subscribers that a room was created
+    pub fn notify_room_created(&self, room_id: &Uuid) {
+        if let Some(room) = self.rooms.get(room_id).filter(|r| r.is_public()) {
+            let command = OutboundEvent::RoomCreated(room.summary(room_id));
+            self.send_room_subscribers_message(&command);
+        }
+    }
+
+    /// Tell lobby subscribers that a room changed
+    pub fn notify_room_updated(&self, room_id: &Uuid) {
+        if let Some(room) = self.rooms.get(room_id).filter(|r| r.is_public()) {
+            let command = OutboundEvent::RoomUpdated(room.summary(room_id));
+            self.send_room_subscribers_message(&command);
+        }
+    }
+
+    /// Drops the room along with everyone's place in it, telling lobby subscribers when it
+    /// was listed
+    pub fn remove_room(&mut self, room_id: &Uuid) {
+        let Some(room) = self.rooms.remove(room_id) else {
+            return;
+        };
+        self.player_rooms.retain(|_, id| id != room_id);
+
+        if room.is_public() {
+            let command = OutboundEvent::RoomRemoved(RoomRemoved { match_id: *room_id });
+            self.send_room_subscribers_message(&command);
+        }
+    }
+
+    fn send_room_subscribers_message(&self, message: &OutboundEvent) {
+        for id in self.room_subscribers.iter() {
+            if let Some(addr) = self.sessions.get(id) {
+                self.send_direct_message(addr, message);
+            }
+        }
+    }
+
+    pub fn presence_state(&self, player_id: &Uuid) -> PresenceState {
+        let Some(room) = self
+            .player_rooms
+            .get(player_id)
+            .and_then(|room_id| self.rooms.get(room_id))
+        else {
+            return PresenceState::Idle;
+        };
+
+        if room.spectators.contains_key(player_id) {
+            return PresenceState::Spectating;
+        }
+        match room.status {
+            GameRoomStatus::Waiting => PresenceState::InQueue,
+            GameRoomStatus::Started => PresenceState::Playing,
+            GameRoomStatus::Finished => PresenceState::Idle,
+        }
+    }
+
+    pub fn is_muted_by(&self, sender_id: &Uuid, recipient_id: &Uuid) -> bool {
+        self.muted_players
+            .get(recipient_id)
+            .map(|muted| muted.contains(sender_id))
+            .unwrap_or(false)
+    }
+
     /// Send message to specific user
-    pub fn send_direct_message(&self, addr: &Recipient<ServerMessage>, message: &str) {
-        addr.do_send(ServerMessage(message.to_owned()));
+    pub fn send_direct_message(&self, addr: &Recipient<ServerMessage>, message: &OutboundEvent) {
+        addr.do_send(ServerMessage(message.clone()));
+    }
+
+    /// Send error to specific user
+    pub fn send_error(&self, player_id: &Uuid, reason: &str) {
+        if let Some(addr) = self.sessions.get(player_id) {
+            let command = OutboundEvent::Error(reason.to_string());
+            self.send_direct_message(addr, &command);
+        }
     }
 }
 
 impl Actor for GameServer {
     type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(self.room_cleanup.interval(), |server, _| {
+            close_idle_rooms(server);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameServer, MAX_IMPORTED_RECORDS};
+    use crate::configuration::RoomCleanupSettings;
+    use crate::game_server::domain::MatchOptions;
+    use crate::game_server::record::{GameRecord, GameResult};
+
+    fn record() -> GameRecord {
+        GameRecord {
+            event: "room".into(),
+            date: "2024.03.09".into(),
+            players: vec![],
+            options: MatchOptions::default(),
+            result: GameResult::Draw,
+            moves: vec![],
+        }
+    }
+
+    #[test]
+    pub fn imports_do_not_push_out_played_games() {
+        let mut server = GameServer::new(
+            Default::default(),
+            RoomCleanupSettings {
+                interval_seconds: 60,
+                waiting_room_timeout_seconds: 60,
+                started_room_timeout_seconds: 60,
+                finished_room_timeout_seconds: 60,
+            },
+        );
+        let played = server.store_game_record(record());
+        let first_import = server.store_imported_record(record());
+
+        for _ in 0..MAX_IMPORTED_RECORDS {
+            server.store_imported_record(record());
+        }
+
+        assert!(server.game_records.contains_key(&played));
+        assert!(!server.game_records.contains_key(&first_import));
+        assert_eq!(server.game_records.len(), MAX_IMPORTED_RECORDS + 1);
+    }
 }
@@ -0,0 +1,201 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::game_server::domain::TeamSymbol;
+
+/// How a game ended for one of its players
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum GameOutcome {
+    Win,
+    Loss,
+    Tie,
+    /// The player left the game before it finished, counted as a loss as well
+    Forfeit,
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SymbolRecord {
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl SymbolRecord {
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+
+        f64::from(self.wins) / f64::from(self.games)
+    }
+}
+
+/// Aggregates of every game a player finished or forfeited
+#[derive(Debug, Default, Clone)]
+pub struct PlayerStats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    pub forfeits: u32,
+    /// Wins in a row up to the last game
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub moves: u32,
+    pub move_time: Duration,
+    pub by_symbol: HashMap<TeamSymbol, SymbolRecord>,
+}
+
+impl PlayerStats {
+    /// Adds a game the player took part in, `symbol` is the one they played if known
+    pub fn record_game(&mut self, symbol: Option<TeamSymbol>, outcome: GameOutcome) {
+        self.games_played += 1;
+        match outcome {
+            GameOutcome::Win => self.wins += 1,
+            GameOutcome::Loss => self.losses += 1,
+            GameOutcome::Tie => self.ties += 1,
+            GameOutcome::Forfeit => {
+                self.losses += 1;
+                self.forfeits += 1;
+            }
+        }
+
+        if outcome == GameOutcome::Win {
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+        } else {
+            self.current_streak = 0;
+        }
+
+        if let Some(symbol) = symbol {
+            let record = self.by_symbol.entry(symbol).or_default();
+            record.games += 1;
+            if outcome == GameOutcome::Win {
+                record.wins += 1;
+            }
+        }
+    }
+
+    /// Adds a move that took `elapsed` since the player's turn started
+    pub fn record_move(&mut self, elapsed: Duration) {
+        self.moves += 1;
+        self.move_time += elapsed;
+    }
+
+    pub fn average_move_time(&self) -> Option<Duration> {
+        (self.moves > 0).then(|| self.move_time / self.moves)
+    }
+
+    /// Stats as sent to clients
+    pub fn summary(&self, player_id: &Uuid) -> StatsSummary {
+        let by_symbol = self
+            .by_symbol
+            .iter()
+            .map(|(symbol, record)| {
+                (
+                    *symbol,
+                    SymbolSummary {
+                        games: record.games,
+                        wins: record.wins,
+                        win_rate: record.win_rate(),
+                    },
+                )
+            })
+            .collect();
+
+        StatsSummary {
+            player_id: *player_id,
+            games_played: self.games_played,
+            wins: self.wins,
+            losses: self.losses,
+            ties: self.ties,
+            forfeits: self.forfeits,
+            current_streak: self.current_streak,
+            best_streak: self.best_streak,
+            average_move_seconds: self.average_move_time().map(|time| time.as_secs_f64()),
+            by_symbol,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSummary {
+    pub player_id: Uuid,
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    pub forfeits: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub average_move_seconds: Option<f64>,
+    pub by_symbol: HashMap<TeamSymbol, SymbolSummary>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolSummary {
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameOutcome, PlayerStats};
+    use crate::game_server::domain::TeamSymbol;
+    use std::time::Duration;
+
+    #[test]
+    pub fn streaks_end_on_anything_but_a_win() {
+        let mut stats = PlayerStats::default();
+        for outcome in [
+            GameOutcome::Win,
+            GameOutcome::Win,
+            GameOutcome::Tie,
+            GameOutcome::Win,
+        ] {
+            stats.record_game(None, outcome);
+        }
+
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 2);
+        assert_eq!(stats.games_played, 4);
+        assert_eq!(stats.ties, 1);
+    }
+
+    #[test]
+    pub fn forfeits_count_as_losses() {
+        let mut stats = PlayerStats::default();
+        stats.record_game(Some(TeamSymbol::Circle), GameOutcome::Forfeit);
+
+        assert_eq!(stats.losses, 1);
+        assert_eq!(stats.forfeits, 1);
+        assert_eq!(stats.by_symbol[&TeamSymbol::Circle].games, 1);
+    }
+
+    #[test]
+    pub fn win_rates_are_kept_per_symbol() {
+        let mut stats = PlayerStats::default();
+        stats.record_game(Some(TeamSymbol::Cross), GameOutcome::Win);
+        stats.record_game(Some(TeamSymbol::Cross), GameOutcome::Loss);
+        stats.record_game(Some(TeamSymbol::Circle), GameOutcome::Win);
+
+        assert_eq!(stats.by_symbol[&TeamSymbol::Cross].win_rate(), 0.5);
+        assert_eq!(stats.by_symbol[&TeamSymbol::Circle].win_rate(), 1.0);
+    }
+
+    #[test]
+    pub fn move_times_are_averaged() {
+        let mut stats = PlayerStats::default();
+
+        assert_eq!(stats.average_move_time(), None);
+
+        stats.record_move(Duration::from_secs(1));
+        stats.record_move(Duration::from_secs(3));
+
+        assert_eq!(stats.average_move_time(), Some(Duration::from_secs(2)));
+    }
+}
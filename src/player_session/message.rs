@@ -1,14 +1,107 @@
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::game_server::board::Cell;
+use crate::game_server::domain::{
+    MatchListQuery, MatchOptions, MoveNotation, ReplayControl, ReplayOptions, RoomReference,
+    TurnInput,
+};
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "message", content = "content")]
 pub enum PlayerMessage {
     Start,
-    List,
+    List(Option<MatchListQuery>),
     Leave,
-    Create(String),
-    Join(Uuid),
-    Turn(String),
+    Create(CreateContent),
+    Join(RoomContent),
+    Spectate(RoomContent),
+    Turn(TurnInput),
+    Collapse(Cell),
     Username(String),
+    Notation(MoveNotation),
+    Chat(String),
+    /// Mutes the player or spectator with the given id in the room, the id can be left out
+    /// when there's a single opponent
+    Mute(Option<Uuid>),
+    Unmute(Option<Uuid>),
+    EnterLobby,
+    LeaveLobby,
+    LobbyChat(String),
+    SubscribeLobby,
+    UnsubscribeLobby,
+    Replay(ReplayContent),
+    ReplayControl(ReplayControl),
+    /// Stats of the given player, or of the sender when no player is given
+    Stats(Option<Uuid>),
+    /// Token from an earlier `Connected` message, the session leaves whatever it was doing and
+    /// carries on as that player
+    Reconnect(Uuid),
+}
+
+/// Either a plain room name or a room name along with its options
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CreateContent {
+    Name(String),
+    Options {
+        name: String,
+        #[serde(flatten)]
+        options: MatchOptions,
+    },
+}
+
+/// Either a room id or invite code, optionally along with the room's password
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum RoomContent {
+    Room(String),
+    WithPassword {
+        room: String,
+        password: Option<String>,
+    },
+}
+
+/// Either a game id or a game id along with how the replay is played
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ReplayContent {
+    GameId(Uuid),
+    WithOptions {
+        #[serde(rename = "gameId")]
+        game_id: Uuid,
+        speed: Option<f64>,
+        #[serde(default)]
+        paused: bool,
+    },
+}
+
+impl From<ReplayContent> for ReplayOptions {
+    fn from(content: ReplayContent) -> Self {
+        match content {
+            ReplayContent::GameId(game_id) => ReplayOptions {
+                game_id,
+                speed: 1.0,
+                paused: false,
+            },
+            ReplayContent::WithOptions {
+                game_id,
+                speed,
+                paused,
+            } => ReplayOptions {
+                game_id,
+                speed: speed.unwrap_or(1.0),
+                paused,
+            },
+        }
+    }
+}
+
+impl RoomContent {
+    pub fn into_parts(self) -> (RoomReference, Option<String>) {
+        match self {
+            Self::Room(room) => (room.as_str().into(), None),
+            Self::WithPassword { room, password } => (room.as_str().into(), password),
+        }
+    }
 }
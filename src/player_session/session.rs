@@ -1,33 +1,55 @@
 use actix::prelude::*;
 use actix_web_actors::ws;
+use std::time::Instant;
 use uuid::Uuid;
 
+use crate::configuration::HeartbeatSettings;
 use crate::game_server;
-use crate::player_session::PlayerMessage;
+use crate::player_session::{CreateContent, PlayerMessage};
 
 /// Define HTTP actor
 pub struct PlayerSession {
     pub id: Uuid,
-    pub team_symbol: Option<game_server::domain::TeamSymbol>,
     pub username: String,
-    pub room_id: Option<Uuid>,
     pub game_server_addr: Addr<game_server::GameServer>,
+    pub heartbeat: HeartbeatSettings,
+    /// Last time the client was heard from, the session is stopped once it's been silent too long
+    pub last_heartbeat: Instant,
+}
+
+impl PlayerSession {
+    /// Pings the client on every heartbeat interval and stops the session if it stopped answering
+    fn start_heartbeat(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        ctx.run_interval(self.heartbeat.interval(), |session, ctx| {
+            if session.last_heartbeat.elapsed() > session.heartbeat.timeout() {
+                tracing::info!(player_session_id = %session.id, "Heartbeat timed out, disconnecting.");
+                ctx.stop();
+                return;
+            }
+
+            ctx.ping(b"");
+        });
+    }
 }
 
 impl Actor for PlayerSession {
     type Context = ws::WebsocketContext<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
+        self.start_heartbeat(ctx);
+
         let session_addr = ctx.address();
         self.game_server_addr
             .send(game_server::events::Connect {
                 id: self.id,
+                token: None,
+                username: self.username.clone(),
                 addr: session_addr.recipient(),
             })
             .into_actor(self)
-            .then(|res, _, ctx| {
+            .then(|res, session, ctx| {
                 match res {
-                    Ok(_) => {}
+                    Ok(id) => session.id = id,
                     _ => ctx.stop(),
                 }
                 fut::ready(())
@@ -37,10 +59,7 @@ impl Actor for PlayerSession {
 
     fn stopping(&mut self, _: &mut Self::Context) -> actix::Running {
         self.game_server_addr
-            .do_send(game_server::events::Disconnect {
-                player_id: self.id,
-                room_id: self.room_id,
-            });
+            .do_send(game_server::events::Disconnect { player_id: self.id });
 
         actix::Running::Stop
     }
@@ -48,6 +67,10 @@ impl Actor for PlayerSession {
 
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
     fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        if msg.is_ok() {
+            self.last_heartbeat = Instant::now();
+        }
+
         match msg {
             Ok(ws::Message::Text(text)) => {
                 let trimmed_text = text.trim();
@@ -55,11 +78,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
                     Ok(message) => match message {
                         PlayerMessage::Start => {
                             self.game_server_addr
-                                .send(game_server::events::StartGame {
-                                    player_id: self.id,
-                                    team_symbol: self.team_symbol,
-                                    room_id: self.room_id,
-                                })
+                                .send(game_server::events::StartGame { player_id: self.id })
                                 .into_actor(self)
                                 .then(|res, _, ctx| {
                                     match res {
@@ -71,13 +90,26 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
                                 .wait(ctx);
                         }
                         PlayerMessage::Turn(turn) => {
-                            let turn = turn.as_str();
                             self.game_server_addr
                                 .send(game_server::events::Turn {
                                     player_id: self.id,
-                                    team_symbol: self.team_symbol,
-                                    turn_move: turn.into(),
-                                    room_id: self.room_id,
+                                    turn_move: turn,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Collapse(cell) => {
+                            self.game_server_addr
+                                .send(game_server::events::Collapse {
+                                    player_id: self.id,
+                                    cell,
                                 })
                                 .into_actor(self)
                                 .then(|res, _, ctx| {
@@ -89,43 +121,61 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
                                 })
                                 .wait(ctx);
                         }
-                        PlayerMessage::Create(room_name) => {
-                            let room_name = room_name.as_str();
+                        PlayerMessage::Create(content) => {
+                            let (room_name, options, legacy_response) = match content {
+                                CreateContent::Name(name) => (name, Default::default(), true),
+                                CreateContent::Options { name, options } => (name, options, false),
+                            };
                             self.game_server_addr
                                 .send(game_server::events::CreateMatch {
                                     id: self.id,
-                                    room_name: room_name.into(),
+                                    room_name,
+                                    options,
+                                    legacy_response,
                                     username: self.username.clone(),
                                 })
                                 .into_actor(self)
-                                .then(|res, session, ctx| {
+                                .then(|res, _, ctx| {
                                     match res {
-                                        Ok(room_id) => {
-                                            session.team_symbol =
-                                                Some(game_server::domain::TeamSymbol::Cross);
-                                            session.room_id = room_id.0;
-                                        }
+                                        Ok(_) => (),
                                         _ => ctx.stop(),
                                     }
                                     fut::ready(())
                                 })
                                 .wait(ctx);
                         }
-                        PlayerMessage::Join(room_id) => {
+                        PlayerMessage::Join(content) => {
+                            let (room, password) = content.into_parts();
                             self.game_server_addr
                                 .send(game_server::events::JoinMatch {
                                     player_id: self.id,
-                                    room_id,
+                                    room,
+                                    password,
                                     username: self.username.clone(),
                                 })
                                 .into_actor(self)
-                                .then(|res, session, ctx| {
+                                .then(|res, _, ctx| {
                                     match res {
-                                        Ok(room_id) => {
-                                            session.team_symbol =
-                                                Some(game_server::domain::TeamSymbol::Circle);
-                                            session.room_id = room_id.0;
-                                        }
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Spectate(content) => {
+                            let (room, password) = content.into_parts();
+                            self.game_server_addr
+                                .send(game_server::events::Spectate {
+                                    player_id: self.id,
+                                    room,
+                                    password,
+                                    username: self.username.clone(),
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
                                         _ => ctx.stop(),
                                     }
                                     fut::ready(())
@@ -134,26 +184,142 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
                         }
                         PlayerMessage::Leave => {
                             self.game_server_addr
-                                .send(game_server::events::LeaveMatch {
+                                .send(game_server::events::LeaveMatch { player_id: self.id })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::List(query) => {
+                            self.game_server_addr
+                                .send(game_server::events::ListMatches {
                                     player_id: self.id,
-                                    room_id: self.room_id,
+                                    query: query.unwrap_or_default(),
                                 })
                                 .into_actor(self)
-                                .then(|res, session, ctx| {
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Chat(message) => {
+                            self.game_server_addr
+                                .send(game_server::events::Chat {
+                                    player_id: self.id,
+                                    username: self.username.clone(),
+                                    message,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Mute(target) => {
+                            self.game_server_addr
+                                .send(game_server::events::MuteOpponent {
+                                    player_id: self.id,
+                                    target,
+                                    mute: true,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Unmute(target) => {
+                            self.game_server_addr
+                                .send(game_server::events::MuteOpponent {
+                                    player_id: self.id,
+                                    target,
+                                    mute: false,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::EnterLobby => {
+                            self.game_server_addr
+                                .send(game_server::events::EnterLobby { player_id: self.id })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::LeaveLobby => {
+                            self.game_server_addr
+                                .send(game_server::events::LeaveLobby { player_id: self.id })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
                                     match res {
-                                        Ok(_) => {
-                                            session.team_symbol = None;
-                                            session.room_id = None;
-                                        }
+                                        Ok(_) => (),
                                         _ => ctx.stop(),
                                     }
                                     fut::ready(())
                                 })
                                 .wait(ctx);
                         }
-                        PlayerMessage::List => {
+                        PlayerMessage::LobbyChat(message) => {
                             self.game_server_addr
-                                .send(game_server::events::ListMatches { player_id: self.id })
+                                .send(game_server::events::LobbyChat {
+                                    player_id: self.id,
+                                    message,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::SubscribeLobby => {
+                            self.game_server_addr
+                                .send(game_server::events::SubscribeLobby { player_id: self.id })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::UnsubscribeLobby => {
+                            self.game_server_addr
+                                .send(game_server::events::UnsubscribeLobby { player_id: self.id })
                                 .into_actor(self)
                                 .then(|res, _, ctx| {
                                     match res {
@@ -165,21 +331,105 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
                                 .wait(ctx);
                         }
                         PlayerMessage::Username(username) => {
-                            let username = username.chars().take(30).collect();
+                            let username: String = username.chars().take(30).collect();
                             let _ = tracing::info_span!(
                                 "Set username",
                                 player_session_id = self.id.to_string(),
                                 username = username
                             )
                             .enter();
+                            self.game_server_addr
+                                .do_send(game_server::events::SetUsername {
+                                    player_id: self.id,
+                                    username: username.clone(),
+                                });
                             self.username = username;
                         }
+                        PlayerMessage::Replay(content) => {
+                            self.game_server_addr
+                                .send(game_server::events::StartReplay {
+                                    player_id: self.id,
+                                    options: content.into(),
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::ReplayControl(control) => {
+                            self.game_server_addr
+                                .send(game_server::events::ControlReplay {
+                                    player_id: self.id,
+                                    control,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Reconnect(token) => {
+                            self.game_server_addr
+                                .send(game_server::events::Reconnect {
+                                    player_id: self.id,
+                                    token,
+                                    username: self.username.clone(),
+                                    addr: ctx.address().recipient(),
+                                })
+                                .into_actor(self)
+                                .then(|res, session, ctx| {
+                                    match res {
+                                        Ok(id) => session.id = id,
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Stats(target) => {
+                            self.game_server_addr
+                                .send(game_server::events::GetStats {
+                                    player_id: self.id,
+                                    target,
+                                })
+                                .into_actor(self)
+                                .then(|res, _, ctx| {
+                                    match res {
+                                        Ok(_) => (),
+                                        _ => ctx.stop(),
+                                    }
+                                    fut::ready(())
+                                })
+                                .wait(ctx);
+                        }
+                        PlayerMessage::Notation(notation) => {
+                            self.game_server_addr
+                                .do_send(game_server::events::SetNotation {
+                                    player_id: self.id,
+                                    notation,
+                                });
+                        }
                     },
                     Err(_) => {
                         tracing::info!("Invalid message {}", trimmed_text);
+                        ctx.notify(game_server::ServerMessage(
+                            game_server::OutboundEvent::Error("Invalid message.".into()),
+                        ));
                     }
                 }
             }
+            Ok(ws::Message::Ping(msg)) => {
+                ctx.pong(&msg);
+            }
             Ok(ws::Message::Close(reason)) => {
                 ctx.close(reason);
                 ctx.stop();
@@ -192,12 +442,6 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for PlayerSession {
 impl Handler<game_server::ServerMessage> for PlayerSession {
     type Result = ();
     fn handle(&mut self, msg: game_server::ServerMessage, ctx: &mut Self::Context) -> Self::Result {
-        if msg.0.contains("PlayerLeft")
-            && self.team_symbol == Some(game_server::domain::TeamSymbol::Circle)
-        {
-            self.team_symbol = Some(game_server::domain::TeamSymbol::Cross);
-        }
-
-        ctx.text(msg.0);
+        ctx.text(serde_json::to_string(&msg.0).unwrap_or_default());
     }
 }
@@ -5,10 +5,12 @@ use actix_web_actors::ws;
 use std::net::TcpListener;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing_actix_web::TracingLogger;
 use uuid::Uuid;
 
-use crate::configuration::ApplicationSettings;
+use crate::configuration::{ApplicationSettings, HeartbeatSettings, RoomCleanupSettings};
+use crate::game_server::events::{GetGameRecord, GetMetrics, GetPlayerStats, ImportGameRecord};
 use crate::game_server::GameServer;
 use crate::player_session::PlayerSession;
 
@@ -26,7 +28,12 @@ impl Application {
 
         let port = listener.local_addr().unwrap().port();
 
-        let server = run(listener).await?;
+        let server = run(
+            listener,
+            configuration.room_cleanup,
+            configuration.heartbeat,
+        )
+        .await?;
 
         Ok(Self { port, server })
     }
@@ -44,13 +51,14 @@ pub async fn index(
     req: HttpRequest,
     stream: web::Payload,
     game_server: web::Data<Addr<GameServer>>,
+    heartbeat: web::Data<HeartbeatSettings>,
 ) -> Result<HttpResponse, Error> {
     let player_session = PlayerSession {
         id: Uuid::new_v4(),
-        team_symbol: None,
         username: String::from("default"),
-        room_id: None,
         game_server_addr: game_server.get_ref().clone(),
+        heartbeat: heartbeat.get_ref().clone(),
+        last_heartbeat: Instant::now(),
     };
     let resp = ws::start(player_session, &req, stream).map_err(|e| {
         tracing::error!("Error starting session {e}");
@@ -60,15 +68,89 @@ pub async fn index(
     resp
 }
 
-pub async fn run(listener: TcpListener) -> Result<Server, anyhow::Error> {
+/// Download the text record of a finished game
+pub async fn download_game_record(
+    game_id: web::Path<Uuid>,
+    game_server: web::Data<Addr<GameServer>>,
+) -> Result<HttpResponse, Error> {
+    let game_id = game_id.into_inner();
+    let record = game_server
+        .send(GetGameRecord(game_id))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(match record {
+        Some(record) => HttpResponse::Ok()
+            .content_type("text/plain; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}.txt\"", game_id),
+            ))
+            .body(record),
+        None => HttpResponse::NotFound().finish(),
+    })
+}
+
+/// Import a game record so it can be downloaded and replayed like games played here
+pub async fn import_game_record(
+    record: String,
+    game_server: web::Data<Addr<GameServer>>,
+) -> Result<HttpResponse, Error> {
+    let imported = game_server
+        .send(ImportGameRecord(record))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(match imported {
+        Ok(game_id) => HttpResponse::Created().json(serde_json::json!({ "gameId": game_id })),
+        Err(reason) => HttpResponse::BadRequest().body(reason),
+    })
+}
+
+/// Stats of a player, by the player id they were sent on connecting
+pub async fn player_stats(
+    player_id: web::Path<Uuid>,
+    game_server: web::Data<Addr<GameServer>>,
+) -> Result<HttpResponse, Error> {
+    let stats = game_server
+        .send(GetPlayerStats(player_id.into_inner()))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(match stats {
+        Some(stats) => HttpResponse::Ok().json(stats),
+        None => HttpResponse::NotFound().finish(),
+    })
+}
+
+/// Counters describing the server's current load
+pub async fn metrics(game_server: web::Data<Addr<GameServer>>) -> Result<HttpResponse, Error> {
+    let metrics = game_server
+        .send(GetMetrics)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(metrics))
+}
+
+pub async fn run(
+    listener: TcpListener,
+    room_cleanup: RoomCleanupSettings,
+    heartbeat: HeartbeatSettings,
+) -> Result<Server, anyhow::Error> {
     let app_state = Arc::new(AtomicUsize::new(0));
-    let game_server = GameServer::new(app_state.clone()).start();
+    let game_server = GameServer::new(app_state.clone(), room_cleanup).start();
 
     let server = HttpServer::new(move || {
         App::new()
             .wrap(TracingLogger::default())
             .app_data(web::Data::new(game_server.clone()))
+            .app_data(web::Data::new(heartbeat.clone()))
             .route("/", web::get().to(index))
+            .route("/games", web::post().to(import_game_record))
+            .route("/games/{game_id}", web::get().to(download_game_record))
+            .route("/players/{player_id}/stats", web::get().to(player_stats))
+            .route("/metrics", web::get().to(metrics))
     })
     .listen(listener)?
     .run();
@@ -0,0 +1,212 @@
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::helpers::*;
+
+#[actix_web::test]
+async fn chat_message_is_relayed_to_everyone_in_the_room() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    send_message(&mut player_one, &build_username_message("playerone")).await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    send_message(&mut player_one, &build_chat_message("good luck")).await;
+
+    for socket in [&mut player_one, &mut player_two, &mut spectator] {
+        let response = process_message(socket).await;
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+        assert_eq!(response["category"], "Chat");
+        assert_eq!(response["body"]["sender"], "playerone");
+        assert!(response["body"]["senderId"].is_string());
+        assert_eq!(response["body"]["message"], "good luck");
+        assert!(response["body"]["timestamp"].is_u64());
+    }
+}
+
+#[actix_web::test]
+async fn chat_message_over_length_limit_is_rejected() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, &build_chat_message(&"a".repeat(201))).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let player_two_response = process_message_result(&mut player_two).await;
+
+    assert_eq!(player_one_response["category"], "Error");
+    assert!(player_two_response.is_none());
+}
+
+#[actix_web::test]
+async fn chat_messages_are_rate_limited() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    for _ in 0..5 {
+        send_message(&mut player_one, &build_chat_message("hello")).await;
+        process_message(&mut player_one).await;
+        process_message(&mut player_two).await;
+    }
+
+    send_message(&mut player_one, &build_chat_message("hello")).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let player_two_response = process_message_result(&mut player_two).await;
+
+    assert_eq!(player_one_response["category"], "Error");
+    assert!(player_two_response.is_none());
+}
+
+#[actix_web::test]
+async fn muted_opponent_messages_are_not_relayed() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, MUTE_MESSAGE).await;
+
+    send_message(&mut player_two, &build_chat_message("hello")).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    let player_one_response = process_message_result(&mut player_one).await;
+
+    assert_eq!(player_two_response["category"], "Chat");
+    assert!(player_one_response.is_none());
+}
+
+#[actix_web::test]
+async fn muting_in_a_three_player_match_needs_a_player_id() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+
+    setup_three_player_game(&mut player_one, &mut player_two, &mut player_three).await;
+
+    send_message(&mut player_one, MUTE_MESSAGE).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_response,
+        serde_json::json!({ "category": "Error", "body": "Name the player to mute." })
+    );
+
+    send_message(&mut player_two, &build_chat_message("hello")).await;
+    process_message(&mut player_two).await;
+    process_message(&mut player_three).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+    let player_two_id = player_one_response["body"]["senderId"].as_str().unwrap();
+
+    send_message(&mut player_one, &build_mute_message(player_two_id)).await;
+
+    send_message(&mut player_two, &build_chat_message("hello")).await;
+    process_message(&mut player_two).await;
+    process_message(&mut player_three).await;
+
+    assert!(process_message_result(&mut player_one).await.is_none());
+
+    send_message(&mut player_three, &build_chat_message("hi")).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_response["category"], "Chat");
+}
+
+/// Reads a chat message from every socket, returning the sender's id
+async fn process_chat_message(
+    sockets: [&mut WebSocketStream<MaybeTlsStream<TcpStream>>; 3],
+) -> String {
+    let mut sender_id = String::new();
+    for socket in sockets {
+        let response = process_message(socket).await;
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+        assert_eq!(response["category"], "Chat");
+        sender_id = response["body"]["senderId"].as_str().unwrap().to_string();
+    }
+
+    sender_id
+}
+
+#[actix_web::test]
+async fn spectators_can_mute_and_be_muted() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    send_message(&mut spectator, &build_chat_message("hi")).await;
+    let spectator_id =
+        process_chat_message([&mut player_one, &mut player_two, &mut spectator]).await;
+    send_message(&mut player_one, &build_chat_message("hello")).await;
+    let player_one_id =
+        process_chat_message([&mut player_one, &mut player_two, &mut spectator]).await;
+
+    send_message(&mut player_one, &build_mute_message(&spectator_id)).await;
+    send_message(&mut spectator, &build_mute_message(&player_one_id)).await;
+
+    send_message(&mut spectator, &build_chat_message("hi again")).await;
+    process_message(&mut player_two).await;
+    process_message(&mut spectator).await;
+
+    assert!(
+        process_message_result(&mut player_one).await.is_none(),
+        "Player one muted the spectator"
+    );
+
+    send_message(&mut player_one, &build_chat_message("hello again")).await;
+    process_message(&mut player_one).await;
+    process_message(&mut player_two).await;
+
+    assert!(
+        process_message_result(&mut spectator).await.is_none(),
+        "The spectator muted player one"
+    );
+}
@@ -1,4 +1,6 @@
-use crate::helpers::{process_message, process_message_result, spawn_app};
+use uuid::Uuid;
+
+use crate::helpers::{process_message, process_message_result, send_message, spawn_app};
 
 #[actix_web::test]
 async fn when_player_connects_they_recieve_confirmation() {
@@ -8,13 +10,11 @@ async fn when_player_connects_they_recieve_confirmation() {
 
     let msg = process_message(&mut player_one).await;
 
-    let expected = serde_json::json!({
-        "category": "Connected",
-        "body": "",
-    });
     let result: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
 
-    assert_eq!(result, expected);
+    assert_eq!(result["category"], "Connected");
+    assert!(Uuid::parse_str(result["body"]["playerId"].as_str().unwrap()).is_ok());
+    assert!(Uuid::parse_str(result["body"]["token"].as_str().unwrap()).is_ok());
 }
 
 #[actix_web::test]
@@ -34,3 +34,21 @@ async fn when_another_player_connects_others_are_not_notified() {
     assert!(player_one_msg.is_none());
     assert!(player_two_msg.is_none());
 }
+
+#[actix_web::test]
+async fn invalid_messages_are_answered_with_an_error() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+
+    let _ = process_message(&mut player_one).await;
+    send_message(&mut player_one, r#"{ "message": "Dance" }"#).await;
+
+    let msg = process_message(&mut player_one).await;
+    let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        msg,
+        serde_json::json!({ "category": "Error", "body": "Invalid message." })
+    );
+}
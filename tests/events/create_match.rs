@@ -1,4 +1,7 @@
-use crate::helpers::{build_create_message, process_message, send_message, spawn_app};
+use crate::helpers::{
+    build_create_message, build_create_message_with_options, process_message, send_message,
+    spawn_app, MatchListResponse, LIST_MESSAGE,
+};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -26,3 +29,102 @@ async fn match_can_be_created() {
     assert_eq!(player_one_response.category, "MatchCreated");
     assert!(Uuid::try_parse(&player_one_response.body).is_ok());
 }
+
+#[actix_web::test]
+async fn match_created_with_options_returns_invite_code() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+
+    let options = serde_json::json!({ "name": "secret room", "password": "hunter2" });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_response["category"], "MatchCreated");
+    assert_eq!(player_one_response["body"]["roomName"], "secret room");
+    assert_eq!(player_one_response["body"]["passwordProtected"], true);
+    assert_eq!(
+        player_one_response["body"]["inviteCode"]
+            .as_str()
+            .unwrap()
+            .len(),
+        6
+    );
+    assert!(player_one_response["body"]["options"]["password"].is_null());
+}
+
+#[actix_web::test]
+async fn private_match_is_not_listed() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    let options = serde_json::json!({ "name": "private room", "private": true });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+    process_message(&mut player_one).await;
+
+    send_message(&mut player_two, LIST_MESSAGE).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: MatchListResponse =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_two_response.body.matches.len(), 0);
+}
+
+#[actix_web::test]
+async fn match_with_invalid_options_is_rejected() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+
+    let options = serde_json::json!({ "name": "room", "seriesLength": 2 });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_response["category"], "Error");
+}
+
+#[actix_web::test]
+async fn creating_another_match_leaves_the_previous_one() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+
+    send_message(&mut player_one, &build_create_message("first room")).await;
+    process_message(&mut player_one).await;
+
+    send_message(&mut player_one, &build_create_message("second room")).await;
+    process_message(&mut player_one).await;
+
+    send_message(&mut player_one, LIST_MESSAGE).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: MatchListResponse =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let room_names: Vec<_> = player_one_response
+        .body
+        .matches
+        .iter()
+        .map(|m| m.room_name.as_str())
+        .collect();
+
+    assert_eq!(room_names, vec!["second room"]);
+}
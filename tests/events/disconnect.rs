@@ -70,7 +70,7 @@ async fn when_player_disconnects_during_match_resets_room() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await;
+    send_turn(&mut player_one, &build_turn_message("MM")).await;
 
     process_message(&mut player_two).await;
 
@@ -90,7 +90,7 @@ async fn when_player_disconnects_during_match_resets_room() {
     process_message(&mut player_one).await;
     process_message(&mut player_three).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
+    send_turn(&mut player_one, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
 
     let player_three_response = process_message(&mut player_three).await;
 
@@ -116,7 +116,7 @@ async fn when_cross_player_disconnects_circle_player_becomes_cross() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await;
+    send_turn(&mut player_one, &build_turn_message("MM")).await;
 
     process_message(&mut player_two).await;
 
@@ -136,7 +136,7 @@ async fn when_cross_player_disconnects_circle_player_becomes_cross() {
     process_message(&mut player_two).await;
     process_message(&mut player_three).await;
 
-    send_message(&mut player_two, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
+    send_turn(&mut player_two, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
 
     let player_three_response = process_message(&mut player_three).await;
 
@@ -0,0 +1,118 @@
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+#[actix_web::test]
+async fn finished_games_can_be_downloaded() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+
+    let response = reqwest::get(format!("{}/games/{}", test_app.http_address, game_id))
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let record = response.text().await.unwrap();
+
+    assert!(record.starts_with("[Event \"room\"]\n"));
+    assert!(record.contains("[Cross \"default\"]\n[Circle \"default\"]\n"));
+    assert!(record.contains("[Variant \"Standard\"]\n"));
+    assert!(record.contains("[Result \"X\"]\n"));
+    assert!(record.ends_with("\n1. X 2,0\n2. O 2,1\n3. X 1,1\n4. O 2,2\n5. X 0,2\n"));
+}
+
+#[actix_web::test]
+async fn downloading_unknown_games_is_not_found() {
+    let test_app = spawn_app().await;
+
+    let response = reqwest::get(format!(
+        "{}/games/{}",
+        test_app.http_address,
+        Uuid::new_v4()
+    ))
+    .await
+    .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[actix_web::test]
+async fn imported_records_round_trip() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+    let client = reqwest::Client::new();
+
+    let record = client
+        .get(format!("{}/games/{}", test_app.http_address, game_id))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .text()
+        .await
+        .unwrap();
+
+    let response = client
+        .post(format!("{}/games", test_app.http_address))
+        .body(record.clone())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 201);
+
+    let imported: serde_json::Value =
+        serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    let imported_record = client
+        .get(format!(
+            "{}/games/{}",
+            test_app.http_address,
+            imported["gameId"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .text()
+        .await
+        .unwrap();
+
+    assert_eq!(imported_record, record);
+}
+
+#[actix_web::test]
+async fn records_with_illegal_moves_are_not_imported() {
+    let test_app = spawn_app().await;
+
+    let record = "[Variant \"Standard\"]\n[Result \"X\"]\n\n1. X 1,1\n2. O 1,1\n";
+    let response = reqwest::Client::new()
+        .post(format!("{}/games", test_app.http_address))
+        .body(record)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(
+        response.text().await.unwrap(),
+        "Move 2 \"O 1,1\" is illegal."
+    );
+}
+
+#[actix_web::test]
+async fn unfinished_records_are_not_imported() {
+    let test_app = spawn_app().await;
+
+    let record = "[Variant \"Standard\"]\n[Result \"X\"]\n\n1. X 1,1\n2. O 0,0\n";
+    let response = reqwest::Client::new()
+        .post(format!("{}/games", test_app.http_address))
+        .body(record)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(
+        response.text().await.unwrap(),
+        "The game isn't finished after its last move."
+    );
+}
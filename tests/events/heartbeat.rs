@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::helpers::*;
+
+/// App pinging every second and hanging up on clients silent for two
+async fn spawn_app_with_short_heartbeat() -> TestApp {
+    spawn_app_with_configuration(|c| {
+        c.heartbeat.interval_seconds = 1;
+        c.heartbeat.timeout_seconds = 2;
+    })
+    .await
+}
+
+/// Keeps reading the socket for `duration` so pings get answered, returning every message read
+async fn read_for(
+    socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    duration: Duration,
+) -> Vec<Message> {
+    let started = Instant::now();
+    let mut messages = vec![];
+
+    while started.elapsed() < duration {
+        if let Some(Ok(msg)) = process_message_result(socket).await {
+            messages.push(msg);
+        }
+    }
+
+    messages
+}
+
+#[actix_web::test]
+async fn player_answering_pings_stays_connected() {
+    let test_app = spawn_app_with_short_heartbeat().await;
+
+    let mut player_one = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+
+    let messages = read_for(&mut player_one, Duration::from_millis(3500)).await;
+
+    assert!(messages.iter().any(|msg| msg.is_ping()));
+
+    send_message(&mut player_one, LIST_MESSAGE).await;
+
+    let messages = read_for(&mut player_one, Duration::from_millis(100)).await;
+
+    assert!(messages.iter().any(|msg| msg.is_text()));
+}
+
+#[actix_web::test]
+async fn silent_player_is_disconnected() {
+    let test_app = spawn_app_with_short_heartbeat().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    // Player 2 stops reading its socket, so its pings go unanswered
+    let messages = read_for(&mut player_one, Duration::from_millis(3500)).await;
+
+    let player_left = messages
+        .iter()
+        .filter(|msg| msg.is_text())
+        .map(|msg| serde_json::from_str::<serde_json::Value>(msg.to_text().unwrap()).unwrap())
+        .any(|msg| msg["category"] == "PlayerLeft");
+
+    assert!(player_left);
+}
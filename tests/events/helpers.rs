@@ -1,6 +1,8 @@
 use futures_util::{SinkExt, StreamExt};
 use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::io::Write;
+use std::sync::Mutex;
 use std::time::Duration;
 use tokio::net::TcpStream;
 use tokio::time::{sleep, timeout};
@@ -9,23 +11,41 @@ use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use url::Url;
 use uuid::Uuid;
 
-use network_tic_tac_toe::configuration::get_configuration;
+use network_tic_tac_toe::configuration::{get_configuration, ApplicationSettings};
 use network_tic_tac_toe::startup::Application;
 use network_tic_tac_toe::telemetry::{get_subscriber, init_subscriber};
 
+/// Everything the apps logged during the test run, so tests can check what ends up in the logs
+static LOGS: Lazy<Mutex<Vec<u8>>> = Lazy::new(Default::default);
+
+struct LogWriter;
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        LOGS.lock().unwrap().extend_from_slice(buf);
+        if std::env::var("TEST_LOG").is_ok() {
+            std::io::stdout().write_all(buf)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 static TRACING: Lazy<()> = Lazy::new(|| {
     let default_filter_level = "info".to_string();
     let subscriber_name = "test".to_string();
 
-    if std::env::var("TEST_LOG").is_ok() {
-        let subscriber = get_subscriber(subscriber_name, default_filter_level, std::io::stdout);
-        init_subscriber(subscriber);
-    } else {
-        let subscriber = get_subscriber(subscriber_name, default_filter_level, std::io::sink);
-        init_subscriber(subscriber);
-    }
+    let subscriber = get_subscriber(subscriber_name, default_filter_level, || LogWriter);
+    init_subscriber(subscriber);
 });
 
+pub fn logs_contain(text: &str) -> bool {
+    String::from_utf8_lossy(&LOGS.lock().unwrap()).contains(text)
+}
+
 #[derive(Deserialize)]
 pub struct MatchListResponse {
     pub category: String,
@@ -33,8 +53,11 @@ pub struct MatchListResponse {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MatchListResponseBody {
     pub matches: Vec<ResponseMatch>,
+    pub total: usize,
+    pub next_cursor: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize)]
@@ -43,14 +66,22 @@ pub struct ResponseMatch {
     pub match_id: Uuid,
     pub room_name: String,
     pub players: String,
+    pub spectators: usize,
     pub status: String,
 }
 
 pub struct TestApp {
     pub address: String,
+    pub http_address: String,
 }
 
 pub async fn spawn_app() -> TestApp {
+    spawn_app_with_configuration(|_| {}).await
+}
+
+pub async fn spawn_app_with_configuration(
+    customize: impl FnOnce(&mut ApplicationSettings),
+) -> TestApp {
     Lazy::force(&TRACING);
 
     let configuration = {
@@ -59,6 +90,7 @@ pub async fn spawn_app() -> TestApp {
         c.port = 0;
         // use localhost for the tests
         c.host = "127.0.0.1".into();
+        customize(&mut c);
 
         c
     };
@@ -68,9 +100,13 @@ pub async fn spawn_app() -> TestApp {
         .expect("Failed to build application");
 
     let address = format!("ws://{}:{}", configuration.host, application.port());
-    let _ = tokio::spawn(application.run_until_stopped());
+    let http_address = format!("http://{}:{}", configuration.host, application.port());
+    tokio::spawn(application.run_until_stopped());
 
-    TestApp { address }
+    TestApp {
+        address,
+        http_address,
+    }
 }
 
 impl TestApp {
@@ -83,26 +119,87 @@ impl TestApp {
     }
 }
 
-pub const START_MESSAGE: &'static str = r#"{ "message": "Start"}"#;
-pub const LIST_MESSAGE: &'static str = r#"{ "message": "List"}"#;
-pub const LEAVE_MESSAGE: &'static str = r#"{ "message": "Leave"}"#;
+pub const START_MESSAGE: &str = r#"{ "message": "Start"}"#;
+pub const LIST_MESSAGE: &str = r#"{ "message": "List"}"#;
+pub const LEAVE_MESSAGE: &str = r#"{ "message": "Leave"}"#;
+pub const MUTE_MESSAGE: &str = r#"{ "message": "Mute"}"#;
+pub const ENTER_LOBBY_MESSAGE: &str = r#"{ "message": "EnterLobby"}"#;
+pub const SUBSCRIBE_LOBBY_MESSAGE: &str = r#"{ "message": "SubscribeLobby"}"#;
+pub const STATS_MESSAGE: &str = r#"{ "message": "Stats"}"#;
+
+pub fn build_list_message(query: serde_json::Value) -> String {
+    serde_json::json!({ "message": "List", "content": query }).to_string()
+}
 
 pub fn build_join_message(match_id: Uuid) -> String {
     format!(r#"{{ "message": "Join", "content": "{}"}}"#, match_id)
 }
 
+pub fn build_join_message_with_password(room: &str, password: &str) -> String {
+    serde_json::json!({
+        "message": "Join",
+        "content": { "room": room, "password": password }
+    })
+    .to_string()
+}
+
+pub fn build_spectate_message(match_id: Uuid) -> String {
+    format!(r#"{{ "message": "Spectate", "content": "{}"}}"#, match_id)
+}
+
 pub fn build_create_message(room: &str) -> String {
     format!(r#"{{ "message": "Create", "content": "{}"}}"#, room)
 }
 
+pub fn build_create_message_with_options(options: serde_json::Value) -> String {
+    serde_json::json!({ "message": "Create", "content": options }).to_string()
+}
+
 pub fn build_turn_message(turn: &str) -> String {
     format!(r#"{{ "message": "Turn", "content": "{}"}}"#, turn)
 }
 
+pub fn build_coordinates_turn_message(row: usize, column: usize) -> String {
+    serde_json::json!({ "message": "Turn", "content": { "row": row, "column": column } })
+        .to_string()
+}
+
 pub fn build_username_message(username: &str) -> String {
     format!(r#"{{ "message": "Username", "content": "{}"}}"#, username)
 }
 
+pub fn build_notation_message(notation: &str) -> String {
+    format!(r#"{{ "message": "Notation", "content": "{}"}}"#, notation)
+}
+
+pub fn build_replay_message(content: serde_json::Value) -> String {
+    serde_json::json!({ "message": "Replay", "content": content }).to_string()
+}
+
+pub fn build_replay_control_message(control: serde_json::Value) -> String {
+    serde_json::json!({ "message": "ReplayControl", "content": control }).to_string()
+}
+
+pub fn build_stats_message(player_id: &str) -> String {
+    format!(r#"{{ "message": "Stats", "content": "{}"}}"#, player_id)
+}
+
+pub fn build_reconnect_message(token: &str) -> String {
+    format!(r#"{{ "message": "Reconnect", "content": "{token}" }}"#)
+}
+
+pub fn build_mute_message(player_id: &str) -> String {
+    format!(r#"{{ "message": "Mute", "content": "{}"}}"#, player_id)
+}
+
+pub fn build_chat_message(message: &str) -> String {
+    format!(r#"{{ "message": "Chat", "content": "{}"}}"#, message)
+}
+
+pub fn build_lobby_chat_message(message: &str) -> String {
+    format!(r#"{{ "message": "LobbyChat", "content": "{}"}}"#, message)
+}
+
 pub async fn process_message(socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>) -> Message {
     timeout(Duration::from_millis(10), socket.next())
         .await
@@ -111,6 +208,20 @@ pub async fn process_message(socket: &mut WebSocketStream<MaybeTlsStream<TcpStre
         .expect("Failed to recieve message in under 10ms")
 }
 
+/// Reads the `Connected` confirmation, returning the player id and reconnect token
+pub async fn process_connected(
+    socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+) -> (String, String) {
+    let msg = process_message(socket).await;
+    let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+    assert_eq!(msg["category"], "Connected");
+
+    (
+        msg["body"]["playerId"].as_str().unwrap().to_string(),
+        msg["body"]["token"].as_str().unwrap().to_string(),
+    )
+}
+
 pub async fn process_message_result(
     socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) -> Option<Result<Message, tokio_tungstenite::tungstenite::Error>> {
@@ -127,38 +238,51 @@ pub async fn send_message(socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>
     sleep(Duration::from_millis(10)).await; // sleep to give time for server to process message
 }
 
+/// Sends a move and reads back the turn the mover is sent for it, returning its body
+pub async fn send_turn(
+    socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    msg: &str,
+) -> serde_json::Value {
+    send_message(socket, msg).await;
+    let turn = process_message(socket).await;
+    let turn: serde_json::Value = serde_json::from_str(turn.to_text().unwrap()).unwrap();
+    assert_eq!(turn["category"], "Turn");
+
+    turn["body"].clone()
+}
+
 pub async fn setup_game(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
     process_message(player_one).await; // Player 1 connects
     process_message(player_two).await; // Player 2 connects
 
-    send_message(&mut player_one, &build_create_message("room")).await;
+    send_message(player_one, &build_create_message("room")).await;
 
-    process_message(&mut player_one).await;
+    process_message(player_one).await;
 
-    send_message(&mut player_two, LIST_MESSAGE).await;
+    send_message(player_two, LIST_MESSAGE).await;
 
-    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response = process_message(player_two).await;
     let player_two_response: MatchListResponse =
         serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
 
     let match_id = player_two_response.body.matches.first().unwrap().match_id;
 
-    send_message(&mut player_two, &build_join_message(match_id)).await;
+    send_message(player_two, &build_join_message(match_id)).await;
 
-    process_message(&mut player_two).await;
-    process_message(&mut player_one).await;
+    process_message(player_two).await;
+    process_message(player_one).await;
 }
 
 pub async fn join_room(
-    mut existing_socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut joining_socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    existing_socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    joining_socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
-    send_message(&mut joining_socket, LIST_MESSAGE).await;
+    send_message(joining_socket, LIST_MESSAGE).await;
 
-    let joining_socket_response = process_message(&mut joining_socket).await;
+    let joining_socket_response = process_message(joining_socket).await;
     let joining_socket_response: MatchListResponse =
         serde_json::from_str(joining_socket_response.to_text().unwrap()).unwrap();
 
@@ -169,118 +293,199 @@ pub async fn join_room(
         .unwrap()
         .match_id;
 
-    send_message(&mut joining_socket, &build_join_message(match_id)).await;
+    send_message(joining_socket, &build_join_message(match_id)).await;
 
-    process_message(&mut joining_socket).await;
-    process_message(&mut existing_socket).await;
+    process_message(joining_socket).await;
+    process_message(existing_socket).await;
+}
+
+pub async fn spectate_room(spectating_socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>) {
+    send_message(spectating_socket, LIST_MESSAGE).await;
+
+    let spectating_socket_response = process_message(spectating_socket).await;
+    let spectating_socket_response: MatchListResponse =
+        serde_json::from_str(spectating_socket_response.to_text().unwrap()).unwrap();
+
+    let match_id = spectating_socket_response
+        .body
+        .matches
+        .first()
+        .unwrap()
+        .match_id;
+
+    send_message(spectating_socket, &build_spectate_message(match_id)).await;
+
+    process_message(spectating_socket).await;
 }
 
 pub async fn setup_and_start_game(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
     setup_game(player_one, player_two).await;
 
-    send_message(&mut player_one, START_MESSAGE).await; // Game start
+    send_message(player_one, START_MESSAGE).await; // Game start
 
-    process_message(&mut player_one).await; // Player 1 recieves game start
-    process_message(&mut player_two).await; // Player 2 recieves game start
+    process_message(player_one).await; // Player 1 recieves game start
+    process_message(player_two).await; // Player 2 recieves game start
 }
 
-pub async fn setup_game_for_tie(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+pub async fn setup_and_start_game_with_options(
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    options: serde_json::Value,
 ) {
-    setup_and_start_game(player_one, player_two).await;
+    process_message(player_one).await; // Player 1 connects
+    process_message(player_two).await; // Player 2 connects
+
+    send_message(player_one, &build_create_message_with_options(options)).await;
+
+    process_message(player_one).await;
 
-    send_message(&mut player_one, &build_turn_message("LL")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("LM")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    join_room(player_one, player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LR")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("UL")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_message(player_one, START_MESSAGE).await; // Game start
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("UR")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    process_message(player_one).await; // Player 1 recieves game start
+    process_message(player_two).await; // Player 2 recieves game start
+}
+
+pub async fn setup_three_player_game(
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_three: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+) {
+    process_message(player_one).await; // Player 1 connects
+    process_message(player_two).await; // Player 2 connects
+    process_message(player_three).await; // Player 3 connects
+
+    let options = serde_json::json!({ "name": "room", "boardSize": 4, "playerCount": 3 });
+    send_message(player_one, &build_create_message_with_options(options)).await;
 
-    send_message(&mut player_one, &build_turn_message("UM")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("MR")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    process_message(player_one).await;
+
+    join_room(player_one, player_two).await;
+    join_room(player_one, player_three).await;
+    process_message(player_two).await; // Player 2 is notified of player 3
+}
+
+pub async fn setup_game_for_tie(
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+) {
+    setup_and_start_game(player_one, player_two).await;
+
+    send_turn(player_one, &build_turn_message("LL")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("LM")).await; // Player 2 turn
+    process_message(player_one).await;
+
+    send_turn(player_one, &build_turn_message("LR")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("UL")).await; // Player 2 turn
+    process_message(player_one).await;
+
+    send_turn(player_one, &build_turn_message("MM")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("UR")).await; // Player 2 turn
+    process_message(player_one).await;
+
+    send_turn(player_one, &build_turn_message("UM")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("MR")).await; // Player 2 turn
+    process_message(player_one).await;
 }
 
 pub async fn setup_game_for_diagonal_victory(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
     setup_and_start_game(player_one, player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LL")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("LM")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("LL")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("LM")).await; // Player 2 turn
+    process_message(player_one).await;
+
+    send_turn(player_one, &build_turn_message("MM")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("LR")).await; // Player 2 turn
+    process_message(player_one).await;
+}
+
+/// Plays a game to a diagonal victory for player one, returning the id of its record
+pub async fn finish_game_and_get_record_id(test_app: &TestApp) -> String {
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("LR")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    setup_game_for_diagonal_victory(&mut player_one, &mut player_two).await;
+
+    send_turn(&mut player_one, &build_turn_message("UR")).await; // Final turn
+    process_message(&mut player_two).await; // Player 2 recieves final turn
+    process_message(&mut player_one).await; // Player 1 recieves victory
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_msg["category"], "GameRecorded");
+
+    player_one_msg["body"]["gameId"]
+        .as_str()
+        .unwrap()
+        .to_string()
 }
 
 pub async fn setup_game_for_diagonal_mirror_victory(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
     setup_and_start_game(player_one, player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("UL")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("LM")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("UL")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("LM")).await; // Player 2 turn
+    process_message(player_one).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("LL")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("MM")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("LL")).await; // Player 2 turn
+    process_message(player_one).await;
 }
 
 pub async fn setup_game_for_cross_victory(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
     setup_and_start_game(player_one, player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LL")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("UL")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("LL")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("UL")).await; // Player 2 turn
+    process_message(player_one).await;
 
-    send_message(&mut player_one, &build_turn_message("LM")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("UM")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("LM")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("UM")).await; // Player 2 turn
+    process_message(player_one).await;
 }
 
 pub async fn setup_game_for_circle_victory(
-    mut player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
-    mut player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_one: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    player_two: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
 ) {
     setup_and_start_game(player_one, player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LL")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("LR")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("LL")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("LR")).await; // Player 2 turn
+    process_message(player_one).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
-    process_message(&mut player_two).await;
-    send_message(&mut player_two, &build_turn_message("MR")).await; // Player 2 turn
-    process_message(&mut player_one).await;
+    send_turn(player_one, &build_turn_message("MM")).await; // Player 1 turn
+    process_message(player_two).await;
+    send_turn(player_two, &build_turn_message("MR")).await; // Player 2 turn
+    process_message(player_one).await;
 
-    send_message(&mut player_one, &build_turn_message("ML")).await; // Player 1 turn
-    process_message(&mut player_two).await;
+    send_turn(player_one, &build_turn_message("ML")).await; // Player 1 turn
+    process_message(player_two).await;
 }
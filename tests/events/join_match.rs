@@ -1,7 +1,10 @@
 use crate::helpers::{
-    build_create_message, build_join_message, build_username_message, process_message,
-    send_message, spawn_app, MatchListResponse, LIST_MESSAGE,
+    build_create_message, build_create_message_with_options, build_join_message,
+    build_join_message_with_password, build_username_message, process_connected, process_message,
+    send_message, setup_game, spawn_app, MatchListResponse, LIST_MESSAGE,
 };
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
 #[actix_web::test]
 async fn existing_match_can_be_joined() {
@@ -10,8 +13,8 @@ async fn existing_match_can_be_joined() {
     let mut player_one = test_app.connect_player().await;
     let mut player_two = test_app.connect_player().await;
 
-    process_message(&mut player_one).await; // Player 1 connects
-    process_message(&mut player_two).await; // Player 2 connects
+    let (player_one_id, _) = process_connected(&mut player_one).await;
+    let (player_two_id, _) = process_connected(&mut player_two).await;
 
     send_message(&mut player_one, &build_username_message("playerone")).await; // Set player one username
     send_message(&mut player_two, &build_username_message("playertwo")).await; // Set player two username
@@ -36,14 +39,19 @@ async fn existing_match_can_be_joined() {
 
     let expected_p1_response = serde_json::json!({
         "category": "PlayerConnected",
-        "body": "playertwo"
+        "body": { "playerId": player_two_id, "username": "playertwo", "symbol": "Circle" }
     });
 
     let player_two_response = process_message(&mut player_two).await;
 
     let expected_p2_response = serde_json::json!({
         "category": "MatchJoined",
-        "body": "playerone"
+        "body": {
+            "players": [
+                { "playerId": player_one_id, "username": "playerone", "symbol": "Cross" },
+                { "playerId": player_two_id, "username": "playertwo", "symbol": "Circle" },
+            ]
+        }
     });
 
     let player_two_response: serde_json::Value =
@@ -52,3 +60,134 @@ async fn existing_match_can_be_joined() {
     assert_eq!(player_two_response, expected_p2_response);
     assert_eq!(player_one_response, expected_p1_response);
 }
+
+async fn create_password_protected_match(
+    player: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+) -> String {
+    let options = serde_json::json!({ "name": "room", "private": true, "password": "hunter2" });
+    send_message(player, &build_create_message_with_options(options)).await;
+
+    let response = process_message(player).await;
+    let response: serde_json::Value = serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+    response["body"]["inviteCode"].as_str().unwrap().to_owned()
+}
+
+#[actix_web::test]
+async fn private_match_can_be_joined_with_invite_code_and_password() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    let invite_code = create_password_protected_match(&mut player_one).await;
+
+    send_message(
+        &mut player_two,
+        &build_join_message_with_password(&invite_code.to_lowercase(), "hunter2"),
+    )
+    .await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_two_response["category"], "MatchJoined");
+}
+
+#[actix_web::test]
+async fn joining_with_wrong_password_returns_error() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    let invite_code = create_password_protected_match(&mut player_one).await;
+
+    send_message(
+        &mut player_two,
+        &build_join_message_with_password(&invite_code, "letmein"),
+    )
+    .await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "Error",
+        "body": "Incorrect password."
+    });
+
+    assert_eq!(player_two_response, expected);
+}
+
+#[actix_web::test]
+async fn joining_a_match_already_joined_returns_error() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_two, LIST_MESSAGE).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: MatchListResponse =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    let match_id = player_two_response.body.matches.first().unwrap().match_id;
+
+    send_message(&mut player_two, &build_join_message(match_id)).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "Error",
+        "body": "You are already in this match."
+    });
+
+    assert_eq!(player_two_response, expected);
+}
+
+#[actix_web::test]
+async fn joining_another_match_leaves_the_previous_one() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+
+    process_message(&mut player_three).await; // Player 3 connects
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_three, &build_create_message("other-room")).await;
+
+    let player_three_response = process_message(&mut player_three).await;
+    let player_three_response: serde_json::Value =
+        serde_json::from_str(player_three_response.to_text().unwrap()).unwrap();
+    let match_id = serde_json::from_value(player_three_response["body"].clone()).unwrap();
+
+    send_message(&mut player_two, &build_join_message(match_id)).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "PlayerLeft",
+        "body": "",
+    });
+
+    assert_eq!(player_one_response, expected);
+}
@@ -58,7 +58,7 @@ async fn when_player_leaves_during_match_resets_room() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await;
+    send_turn(&mut player_one, &build_turn_message("MM")).await;
 
     process_message(&mut player_two).await;
 
@@ -73,7 +73,7 @@ async fn when_player_leaves_during_match_resets_room() {
     process_message(&mut player_one).await;
     process_message(&mut player_three).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
+    send_turn(&mut player_one, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
 
     let player_three_response = process_message(&mut player_three).await;
 
@@ -99,7 +99,7 @@ async fn when_cross_player_leaves_circle_player_becomes_cross() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await;
+    send_turn(&mut player_one, &build_turn_message("MM")).await;
 
     process_message(&mut player_two).await;
 
@@ -114,7 +114,7 @@ async fn when_cross_player_leaves_circle_player_becomes_cross() {
     process_message(&mut player_two).await;
     process_message(&mut player_three).await;
 
-    send_message(&mut player_two, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
+    send_turn(&mut player_two, &build_turn_message("MM")).await; // Duplicate turn if room had not reset.
 
     let player_three_response = process_message(&mut player_three).await;
 
@@ -165,7 +165,7 @@ async fn when_player_leaves_they_can_join_another_match() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await; // Player 1 and 2 are in a match
 
-    process_message(&mut player_three).await; // Player 3 connects
+    let (player_three_id, _) = process_connected(&mut player_three).await;
 
     send_message(&mut player_three, &build_username_message("playerthree")).await; // Set player three username
 
@@ -192,15 +192,20 @@ async fn when_player_leaves_they_can_join_another_match() {
     send_message(&mut player_two, &build_join_message(match_id)).await;
 
     let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+    let player_two_id = player_two_response["body"]["players"][1]["playerId"].clone();
 
     let expected_p2_response = serde_json::json!({
         "category": "MatchJoined",
-        "body": "playerthree"
+        "body": {
+            "players": [
+                { "playerId": player_three_id, "username": "playerthree", "symbol": "Cross" },
+                { "playerId": player_two_id, "username": "default", "symbol": "Circle" },
+            ]
+        }
     });
 
-    let player_two_response: serde_json::Value =
-        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
-
     assert_eq!(player_two_response, expected_p2_response);
 }
 
@@ -213,11 +218,12 @@ async fn when_player_leaves_after_match_is_over_does_not_reset_room() {
 
     setup_game_for_cross_victory(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LR")).await; // Final turn
+    send_turn(&mut player_one, &build_turn_message("LR")).await; // Final turn
     process_message(&mut player_two).await; // Player 2 recieves final turn
 
     process_message(&mut player_one).await;
     process_message(&mut player_two).await;
+    process_message(&mut player_two).await; // Player 2 recieves the game record id
 
     send_message(&mut player_two, LEAVE_MESSAGE).await; // Player 2 leaves
 
@@ -236,3 +242,48 @@ async fn when_player_leaves_after_match_is_over_does_not_reset_room() {
 
     assert_eq!(finished_game_count, 1);
 }
+
+#[actix_web::test]
+async fn when_player_leaves_later_players_move_up_a_seat() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+    let mut player_four = test_app.connect_player().await;
+
+    process_message(&mut player_four).await; // Player 4 connects
+
+    setup_three_player_game(&mut player_one, &mut player_two, &mut player_three).await;
+
+    send_message(&mut player_two, LEAVE_MESSAGE).await; // Circle player leaves
+    process_message(&mut player_one).await;
+    process_message(&mut player_three).await;
+
+    join_room(&mut player_one, &mut player_four).await;
+    process_message(&mut player_three).await; // Player 3 is notified of player 4
+
+    send_message(&mut player_one, START_MESSAGE).await;
+    process_message(&mut player_one).await;
+    process_message(&mut player_three).await;
+    process_message(&mut player_four).await;
+
+    send_turn(&mut player_one, &build_coordinates_turn_message(0, 0)).await;
+    process_message(&mut player_three).await;
+    process_message(&mut player_four).await;
+
+    send_turn(&mut player_three, &build_coordinates_turn_message(1, 1)).await; // Player 3 is now Circle
+    process_message(&mut player_one).await;
+    process_message(&mut player_four).await;
+
+    send_turn(&mut player_four, &build_coordinates_turn_message(2, 2)).await; // Player 4 takes Triangle
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "Turn", "body": { "row": 2, "column": 2 } })
+    );
+}
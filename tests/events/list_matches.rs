@@ -1,5 +1,6 @@
 use crate::helpers::{
-    build_create_message, process_message, send_message, spawn_app, MatchListResponse, LIST_MESSAGE,
+    build_create_message, build_create_message_with_options, build_list_message, process_message,
+    send_message, setup_game, spawn_app, MatchListResponse, LIST_MESSAGE,
 };
 
 #[actix_web::test]
@@ -61,3 +62,130 @@ async fn when_matches_exists_returns_them() {
         .find(|m| m.room_name == "player-2-room" && m.status == "Waiting" && m.players == "1/2")
         .is_some());
 }
+
+#[actix_web::test]
+async fn match_options_are_listed() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    let options = serde_json::json!({
+        "name": "rated room",
+        "rated": true,
+        "timeControl": { "initialSeconds": 300, "incrementSeconds": 5 },
+        "seriesLength": 3,
+    });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+    process_message(&mut player_one).await;
+
+    send_message(&mut player_two, LIST_MESSAGE).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    let listed_options = &player_two_response["body"]["matches"][0]["options"];
+
+    assert_eq!(listed_options["variant"], "Standard");
+    assert_eq!(listed_options["boardSize"], 3);
+    assert_eq!(listed_options["rated"], true);
+    assert_eq!(listed_options["timeControl"]["initialSeconds"], 300);
+    assert_eq!(listed_options["seriesLength"], 3);
+    assert_eq!(listed_options["firstMove"], "Creator");
+}
+
+#[actix_web::test]
+async fn matches_can_be_filtered() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+    let mut player_four = test_app.connect_player().await;
+
+    process_message(&mut player_four).await; // Player 4 connects
+
+    setup_game(&mut player_one, &mut player_two).await; // Full room
+
+    process_message(&mut player_three).await; // Player 3 connects
+    send_message(&mut player_three, &build_create_message("Open Room")).await;
+    process_message(&mut player_three).await;
+
+    let query = serde_json::json!({ "joinableOnly": true });
+    send_message(&mut player_four, &build_list_message(query)).await;
+
+    let response = process_message(&mut player_four).await;
+    let response: MatchListResponse = serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+    assert_eq!(response.body.total, 1);
+    assert_eq!(
+        response.body.matches.first().unwrap().room_name,
+        "Open Room"
+    );
+
+    let query = serde_json::json!({ "search": "open" });
+    send_message(&mut player_four, &build_list_message(query)).await;
+
+    let response = process_message(&mut player_four).await;
+    let response: MatchListResponse = serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+    assert_eq!(response.body.total, 1);
+    assert_eq!(
+        response.body.matches.first().unwrap().room_name,
+        "Open Room"
+    );
+}
+
+#[actix_web::test]
+async fn matches_are_paginated_with_cursor() {
+    let test_app = spawn_app().await;
+
+    let mut lister = test_app.connect_player().await;
+    process_message(&mut lister).await; // Lister connects
+
+    let mut players = vec![]; // Keep the connections, and so the rooms, open
+    for name in ["room-1", "room-2", "room-3"] {
+        let mut player = test_app.connect_player().await;
+        process_message(&mut player).await;
+        send_message(&mut player, &build_create_message(name)).await;
+        process_message(&mut player).await;
+        players.push(player);
+    }
+
+    let query = serde_json::json!({ "sort": "Oldest", "limit": 2 });
+    send_message(&mut lister, &build_list_message(query)).await;
+
+    let response = process_message(&mut lister).await;
+    let response: MatchListResponse = serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+    let names: Vec<&str> = response
+        .body
+        .matches
+        .iter()
+        .map(|m| m.room_name.as_str())
+        .collect();
+
+    assert_eq!(response.body.total, 3);
+    assert_eq!(names, vec!["room-1", "room-2"]);
+
+    let cursor = response.body.next_cursor.expect("Expected a next cursor");
+    let query = serde_json::json!({ "sort": "Oldest", "limit": 2, "cursor": cursor });
+    send_message(&mut lister, &build_list_message(query)).await;
+
+    let response = process_message(&mut lister).await;
+    let response: MatchListResponse = serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+    let names: Vec<&str> = response
+        .body
+        .matches
+        .iter()
+        .map(|m| m.room_name.as_str())
+        .collect();
+
+    assert_eq!(names, vec!["room-3"]);
+    assert!(response.body.next_cursor.is_none());
+}
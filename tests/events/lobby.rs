@@ -0,0 +1,143 @@
+use crate::helpers::*;
+
+#[actix_web::test]
+async fn entering_lobby_returns_presence_list() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    send_message(&mut player_one, &build_username_message("playerone")).await;
+    send_message(&mut player_two, &build_username_message("playertwo")).await;
+
+    send_message(&mut player_one, ENTER_LOBBY_MESSAGE).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_response["category"], "Presence");
+
+    let players = player_one_response["body"]["players"].as_array().unwrap();
+
+    assert_eq!(players.len(), 2);
+    assert!(players
+        .iter()
+        .any(|player| player["username"] == "playerone" && player["state"] == "Idle"));
+    assert!(players
+        .iter()
+        .any(|player| player["username"] == "playertwo" && player["state"] == "Idle"));
+    assert!(players.iter().all(|player| player["playerId"].is_string()));
+}
+
+#[actix_web::test]
+async fn presence_is_pushed_when_player_state_changes() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    send_message(&mut player_two, &build_username_message("playertwo")).await;
+
+    send_message(&mut player_one, ENTER_LOBBY_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 recieves presence list
+
+    send_message(&mut player_two, &build_create_message("room")).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let players = player_one_response["body"]["players"].as_array().unwrap();
+
+    assert_eq!(player_one_response["category"], "Presence");
+    assert!(players
+        .iter()
+        .any(|player| player["username"] == "playertwo" && player["state"] == "InQueue"));
+}
+
+#[actix_web::test]
+async fn lobby_chat_is_relayed_to_lobby_members_only() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+    process_message(&mut player_three).await; // Player 3 connects
+
+    send_message(&mut player_one, &build_username_message("playerone")).await;
+
+    send_message(&mut player_one, ENTER_LOBBY_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 recieves presence list
+
+    send_message(&mut player_two, ENTER_LOBBY_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 recieves presence list
+    process_message(&mut player_two).await; // Player 2 recieves presence list
+
+    send_message(
+        &mut player_one,
+        &build_lobby_chat_message("anyone up for a game?"),
+    )
+    .await;
+
+    for socket in [&mut player_one, &mut player_two] {
+        let response = process_message(socket).await;
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+
+        assert_eq!(response["category"], "LobbyChat");
+        assert_eq!(response["body"]["sender"], "playerone");
+        assert_eq!(response["body"]["message"], "anyone up for a game?");
+    }
+
+    let player_three_response = process_message_result(&mut player_three).await;
+
+    assert!(player_three_response.is_none());
+}
+
+#[actix_web::test]
+async fn players_in_a_room_are_out_of_the_lobby_until_they_leave() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    send_message(&mut player_one, ENTER_LOBBY_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 recieves presence list
+
+    send_message(&mut player_two, ENTER_LOBBY_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 recieves presence list
+    process_message(&mut player_two).await; // Player 2 recieves presence list
+
+    send_message(&mut player_two, &build_create_message("room")).await;
+    process_message(&mut player_one).await; // Player 1 recieves presence list
+    process_message(&mut player_two).await; // Player 2 recieves the created match
+
+    send_message(&mut player_one, &build_lobby_chat_message("anyone?")).await;
+    process_message(&mut player_one).await; // Player 1 recieves their own message
+
+    assert!(
+        process_message_result(&mut player_two).await.is_none(),
+        "Player 2 is in a room"
+    );
+
+    send_message(&mut player_two, LEAVE_MESSAGE).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_two_response["category"], "Presence");
+}
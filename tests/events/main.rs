@@ -1,9 +1,18 @@
+mod chat;
 mod connect;
 mod create_match;
 mod disconnect;
+mod game_record;
+mod heartbeat;
 mod helpers;
 mod join_match;
 mod leave_match;
 mod list_matches;
+mod lobby;
+mod replay;
+mod room_cleanup;
+mod spectate;
 mod start_game;
+mod stats;
+mod subscribe_lobby;
 mod turn;
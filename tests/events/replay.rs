@@ -0,0 +1,168 @@
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::sleep;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+async fn next_json(socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>) -> serde_json::Value {
+    let msg = process_message(socket).await;
+    serde_json::from_str(msg.to_text().unwrap()).unwrap()
+}
+
+fn turn(body: &str) -> serde_json::Value {
+    serde_json::json!({ "category": "Turn", "body": body })
+}
+
+fn game_start() -> serde_json::Value {
+    serde_json::json!({ "category": "GameStart", "body": "" })
+}
+
+#[actix_web::test]
+async fn replays_can_be_stepped_through() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+
+    let mut viewer = test_app.connect_player().await;
+    process_message(&mut viewer).await; // Viewer connects
+
+    let replay = serde_json::json!({ "gameId": game_id, "paused": true });
+    send_message(&mut viewer, &build_replay_message(replay)).await;
+
+    assert_eq!(next_json(&mut viewer).await, game_start());
+    assert_eq!(
+        next_json(&mut viewer).await,
+        serde_json::json!({
+            "category": "Replay",
+            "body": { "gameId": game_id, "position": 0, "total": 5, "paused": true, "speed": 1.0 }
+        })
+    );
+
+    let step_forward = build_replay_control_message(serde_json::json!("StepForward"));
+    send_message(&mut viewer, &step_forward).await;
+    assert_eq!(next_json(&mut viewer).await, turn("LL"));
+    assert_eq!(next_json(&mut viewer).await["body"]["position"], 1);
+
+    send_message(&mut viewer, &step_forward).await;
+    assert_eq!(next_json(&mut viewer).await, turn("LM"));
+    assert_eq!(next_json(&mut viewer).await["body"]["position"], 2);
+
+    let step_back = build_replay_control_message(serde_json::json!("StepBack"));
+    send_message(&mut viewer, &step_back).await;
+    assert_eq!(next_json(&mut viewer).await, game_start());
+    assert_eq!(next_json(&mut viewer).await, turn("LL"));
+    assert_eq!(next_json(&mut viewer).await["body"]["position"], 1);
+
+    let seek = build_replay_control_message(serde_json::json!({ "Seek": 5 }));
+    send_message(&mut viewer, &seek).await;
+    assert_eq!(next_json(&mut viewer).await, game_start());
+    for code in ["LL", "LM", "MM", "LR", "UR"] {
+        assert_eq!(next_json(&mut viewer).await, turn(code));
+    }
+    assert_eq!(next_json(&mut viewer).await["body"]["position"], 5);
+}
+
+#[actix_web::test]
+async fn replays_play_at_the_chosen_speed() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+
+    let mut viewer = test_app.connect_player().await;
+    process_message(&mut viewer).await; // Viewer connects
+
+    let replay = serde_json::json!({ "gameId": game_id, "speed": 16.0 });
+    send_message(&mut viewer, &build_replay_message(replay)).await;
+
+    assert_eq!(next_json(&mut viewer).await, game_start());
+    assert_eq!(next_json(&mut viewer).await["body"]["paused"], false);
+
+    sleep(Duration::from_millis(500)).await; // Moves are 62.5ms apart at speed 16
+
+    for code in ["LL", "LM", "MM", "LR", "UR"] {
+        assert_eq!(next_json(&mut viewer).await, turn(code));
+    }
+    assert_eq!(next_json(&mut viewer).await["body"]["position"], 5);
+    assert!(
+        process_message_result(&mut viewer).await.is_none(),
+        "Replay stops after the last move"
+    );
+}
+
+#[actix_web::test]
+async fn paused_replays_do_not_play() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+
+    let mut viewer = test_app.connect_player().await;
+    process_message(&mut viewer).await; // Viewer connects
+
+    let replay = serde_json::json!({ "gameId": game_id, "speed": 16.0 });
+    send_message(&mut viewer, &build_replay_message(replay)).await;
+    let pause = build_replay_control_message(serde_json::json!("Pause"));
+    send_message(&mut viewer, &pause).await;
+
+    assert_eq!(next_json(&mut viewer).await, game_start());
+    assert_eq!(next_json(&mut viewer).await["body"]["paused"], false);
+    assert_eq!(next_json(&mut viewer).await["body"]["paused"], true);
+
+    sleep(Duration::from_millis(200)).await;
+
+    assert!(
+        process_message_result(&mut viewer).await.is_none(),
+        "Paused replays send no moves"
+    );
+}
+
+#[actix_web::test]
+async fn invalid_replays_are_rejected() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+
+    let mut viewer = test_app.connect_player().await;
+    process_message(&mut viewer).await; // Viewer connects
+
+    send_message(
+        &mut viewer,
+        &build_replay_message(serde_json::json!(Uuid::new_v4())),
+    )
+    .await;
+    assert_eq!(
+        next_json(&mut viewer).await,
+        serde_json::json!({ "category": "Error", "body": "Game not found." })
+    );
+
+    let replay = serde_json::json!({ "gameId": game_id, "speed": 100.0 });
+    send_message(&mut viewer, &build_replay_message(replay)).await;
+    assert_eq!(
+        next_json(&mut viewer).await,
+        serde_json::json!({ "category": "Error", "body": "Replay speed must be between 0.25 and 16." })
+    );
+
+    let step_forward = build_replay_control_message(serde_json::json!("StepForward"));
+    send_message(&mut viewer, &step_forward).await;
+    assert_eq!(
+        next_json(&mut viewer).await,
+        serde_json::json!({ "category": "Error", "body": "No replay is running." })
+    );
+}
+
+#[actix_web::test]
+async fn players_in_a_started_game_cant_watch_replays() {
+    let test_app = spawn_app().await;
+    let game_id = finish_game_and_get_record_id(&test_app).await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    let replay = serde_json::json!({ "gameId": game_id });
+    send_message(&mut player_one, &build_replay_message(replay)).await;
+
+    assert_eq!(
+        next_json(&mut player_one).await,
+        serde_json::json!({ "category": "Error", "body": "You can't watch a replay during a game." })
+    );
+    assert!(process_message_result(&mut player_two).await.is_none());
+}
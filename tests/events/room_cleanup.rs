@@ -0,0 +1,99 @@
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::helpers::*;
+
+/// App sweeping rooms every second, closing waiting rooms right away
+async fn spawn_app_closing_waiting_rooms() -> TestApp {
+    spawn_app_with_configuration(|c| {
+        c.room_cleanup.interval_seconds = 1;
+        c.room_cleanup.waiting_room_timeout_seconds = 0;
+    })
+    .await
+}
+
+#[actix_web::test]
+async fn idle_waiting_room_is_closed() {
+    let test_app = spawn_app_closing_waiting_rooms().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    send_message(&mut player_one, &build_create_message("idle room")).await;
+    process_message(&mut player_one).await;
+
+    sleep(Duration::from_millis(1100)).await; // Wait for the sweep
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "RoomClosed",
+        "body": { "reason": "The match was not started in time." },
+    });
+
+    assert_eq!(player_one_response, expected);
+
+    send_message(&mut player_two, LIST_MESSAGE).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: MatchListResponse =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_two_response.body.matches.len(), 0);
+}
+
+#[actix_web::test]
+async fn closed_rooms_are_counted_in_metrics() {
+    let test_app = spawn_app_closing_waiting_rooms().await;
+
+    let mut player_one = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+
+    send_message(&mut player_one, &build_create_message("idle room")).await;
+    process_message(&mut player_one).await;
+
+    sleep(Duration::from_millis(1100)).await; // Wait for the sweep
+
+    let response = reqwest::get(format!("{}/metrics", test_app.http_address))
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+    assert_eq!(body["roomsCreated"], 1);
+    assert_eq!(body["roomsReaped"], 1);
+    assert_eq!(body["openRooms"], 0);
+}
+
+#[actix_web::test]
+async fn started_game_is_kept_until_its_own_timeout() {
+    let test_app = spawn_app_closing_waiting_rooms().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    sleep(Duration::from_millis(1100)).await; // Wait for the sweep
+
+    send_turn(&mut player_one, &build_turn_message("MM")).await;
+
+    let player_two_response = process_message(&mut player_two).await;
+    let player_two_response: serde_json::Value =
+        serde_json::from_str(player_two_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "Turn",
+        "body": "MM",
+    });
+
+    assert_eq!(player_two_response, expected);
+}
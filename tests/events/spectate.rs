@@ -0,0 +1,231 @@
+use crate::helpers::*;
+
+#[actix_web::test]
+async fn spectator_recieves_board_snapshot() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    send_message(&mut player_one, &build_username_message("playerone")).await;
+    send_message(&mut player_two, &build_username_message("playertwo")).await;
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    send_turn(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
+    process_message(&mut player_two).await;
+
+    send_message(&mut spectator, LIST_MESSAGE).await;
+
+    let spectator_response = process_message(&mut spectator).await;
+    let spectator_response: MatchListResponse =
+        serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+    let match_id = spectator_response.body.matches.first().unwrap().match_id;
+
+    send_message(&mut spectator, &build_spectate_message(match_id)).await;
+
+    let spectator_response = process_message(&mut spectator).await;
+    let spectator_response: serde_json::Value =
+        serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(spectator_response["category"], "MatchSpectated");
+    assert_eq!(spectator_response["body"]["status"], "Started");
+    assert_eq!(spectator_response["body"]["currentTurn"], "Circle");
+    assert_eq!(spectator_response["body"]["board"]["MM"], "playerone");
+    assert_eq!(
+        spectator_response["body"]["players"][0]["username"],
+        "playerone"
+    );
+    assert_eq!(spectator_response["body"]["players"][0]["symbol"], "Cross");
+    assert_eq!(
+        spectator_response["body"]["players"][1]["username"],
+        "playertwo"
+    );
+    assert_eq!(spectator_response["body"]["players"][1]["symbol"], "Circle");
+}
+
+#[actix_web::test]
+async fn spectating_your_own_match_is_rejected() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, LIST_MESSAGE).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: MatchListResponse =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    let match_id = player_one_response.body.matches.first().unwrap().match_id;
+
+    send_message(&mut player_one, &build_spectate_message(match_id)).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_response,
+        serde_json::json!({ "category": "Error", "body": "You are already in this match." })
+    );
+}
+
+#[actix_web::test]
+async fn spectator_recieves_turns_and_game_over() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    for (player, turn) in [(1, "LL"), (2, "UL"), (1, "LM"), (2, "UM"), (1, "LR")] {
+        if player == 1 {
+            send_turn(&mut player_one, &build_turn_message(turn)).await;
+        } else {
+            send_turn(&mut player_two, &build_turn_message(turn)).await;
+        }
+
+        let spectator_response = process_message(&mut spectator).await;
+        let spectator_response: serde_json::Value =
+            serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+        assert_eq!(
+            spectator_response,
+            serde_json::json!({ "category": "Turn", "body": turn })
+        );
+    }
+
+    let spectator_response = process_message(&mut spectator).await;
+    let spectator_response: serde_json::Value =
+        serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "GameOver",
+        "body": { "winner": "default" }
+    });
+
+    assert_eq!(spectator_response, expected);
+}
+
+#[actix_web::test]
+async fn spectator_cant_make_a_move() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    send_message(&mut spectator, &build_turn_message("MM")).await;
+
+    let player_one_response = process_message_result(&mut player_one).await;
+    let player_two_response = process_message_result(&mut player_two).await;
+
+    assert!(player_one_response.is_none());
+    assert!(player_two_response.is_none());
+}
+
+#[actix_web::test]
+async fn spectators_are_counted_in_match_list() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    send_message(&mut spectator, LIST_MESSAGE).await;
+
+    let spectator_response = process_message(&mut spectator).await;
+    let spectator_response: MatchListResponse =
+        serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        spectator_response.body.matches.first().unwrap().spectators,
+        1
+    );
+}
+
+#[actix_web::test]
+async fn spectator_is_told_when_a_player_leaves() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    send_message(&mut player_two, LEAVE_MESSAGE).await;
+    process_message(&mut player_one).await;
+
+    let spectator_response = process_message(&mut spectator).await;
+    let spectator_response: serde_json::Value =
+        serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "PlayerLeft",
+        "body": "",
+    });
+
+    assert_eq!(spectator_response, expected);
+}
+
+#[actix_web::test]
+async fn spectator_is_told_when_the_room_is_removed() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut spectator = test_app.connect_player().await;
+
+    process_message(&mut spectator).await; // Spectator connects
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    spectate_room(&mut spectator).await;
+
+    send_message(&mut player_two, LEAVE_MESSAGE).await;
+    process_message(&mut player_one).await;
+    process_message(&mut spectator).await; // Player 2 left
+
+    send_message(&mut player_one, LEAVE_MESSAGE).await;
+
+    let spectator_response = process_message(&mut spectator).await;
+    let spectator_response: serde_json::Value =
+        serde_json::from_str(spectator_response.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "RoomClosed",
+        "body": { "reason": "All players left." },
+    });
+
+    assert_eq!(spectator_response, expected);
+}
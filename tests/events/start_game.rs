@@ -1,6 +1,7 @@
 use crate::helpers::{
-    build_create_message, build_join_message, process_message, process_message_result,
-    send_message, spawn_app, MatchListResponse, LIST_MESSAGE, START_MESSAGE,
+    build_create_message, build_create_message_with_options, build_join_message,
+    build_turn_message, join_room, process_message, process_message_result, send_message,
+    send_turn, spawn_app, MatchListResponse, LIST_MESSAGE, START_MESSAGE,
 };
 
 #[actix_web::test]
@@ -94,3 +95,75 @@ async fn cross_player_can_start_game() {
     assert_eq!(player_one_response, expected);
     assert_eq!(player_two_response, expected);
 }
+
+#[actix_web::test]
+async fn joiner_moves_first_when_configured() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+
+    let options = serde_json::json!({ "name": "room", "firstMove": "Joiner" });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+    process_message(&mut player_one).await;
+
+    join_room(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, START_MESSAGE).await;
+    process_message(&mut player_one).await;
+    process_message(&mut player_two).await;
+
+    send_message(&mut player_one, &build_turn_message("MM")).await; // Not player 1's turn
+
+    let player_two_response = process_message_result(&mut player_two).await;
+
+    assert!(player_two_response.is_none());
+
+    send_turn(&mut player_two, &build_turn_message("MM")).await;
+
+    let player_one_response = process_message(&mut player_one).await;
+    let player_one_response: serde_json::Value =
+        serde_json::from_str(player_one_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_response,
+        serde_json::json!({ "category": "Turn", "body": "MM" })
+    );
+}
+
+#[actix_web::test]
+async fn three_player_games_start_once_every_seat_is_taken() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut player_two).await; // Player 2 connects
+    process_message(&mut player_three).await; // Player 3 connects
+
+    let options = serde_json::json!({ "name": "room", "boardSize": 4, "playerCount": 3 });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+    process_message(&mut player_one).await;
+
+    join_room(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, START_MESSAGE).await; // A seat is still empty
+    assert!(process_message_result(&mut player_two).await.is_none());
+
+    join_room(&mut player_one, &mut player_three).await;
+    process_message(&mut player_two).await; // Player 2 is notified of player 3
+
+    send_message(&mut player_one, START_MESSAGE).await;
+
+    for player in [&mut player_one, &mut player_two, &mut player_three] {
+        let response = process_message(player).await;
+        let response: serde_json::Value =
+            serde_json::from_str(response.to_text().unwrap()).unwrap();
+        assert_eq!(response["category"], "GameStart");
+    }
+}
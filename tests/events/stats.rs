@@ -0,0 +1,173 @@
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::sleep;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+use crate::helpers::*;
+
+async fn own_stats(socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>) -> serde_json::Value {
+    send_message(socket, STATS_MESSAGE).await;
+
+    let msg = process_message(socket).await;
+    let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+    assert_eq!(msg["category"], "Stats");
+
+    msg["body"].clone()
+}
+
+#[actix_web::test]
+async fn finished_games_are_added_to_player_stats() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_game_for_diagonal_victory(&mut player_one, &mut player_two).await;
+
+    send_turn(&mut player_one, &build_turn_message("UR")).await; // Final turn
+    process_message(&mut player_two).await; // Player 2 recieves final turn
+    for socket in [&mut player_one, &mut player_two] {
+        process_message(socket).await; // Game over
+        process_message(socket).await; // Game record id
+    }
+
+    let player_one_stats = own_stats(&mut player_one).await;
+
+    assert_eq!(player_one_stats["gamesPlayed"], 1);
+    assert_eq!(player_one_stats["wins"], 1);
+    assert_eq!(player_one_stats["currentStreak"], 1);
+    assert_eq!(player_one_stats["bySymbol"]["Cross"]["winRate"], 1.0);
+    assert!(player_one_stats["averageMoveSeconds"].is_f64());
+
+    let player_two_id = own_stats(&mut player_two).await["playerId"].clone();
+    let player_two_id = player_two_id.as_str().unwrap();
+
+    send_message(&mut player_one, &build_stats_message(player_two_id)).await;
+    let msg = process_message(&mut player_one).await;
+    let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(msg["body"]["losses"], 1);
+    assert_eq!(msg["body"]["bySymbol"]["Circle"]["winRate"], 0.0);
+
+    let response = reqwest::get(format!(
+        "{}/players/{}/stats",
+        test_app.http_address, player_two_id
+    ))
+    .await
+    .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+    assert_eq!(body, msg["body"]);
+}
+
+#[actix_web::test]
+async fn leaving_a_started_game_is_a_forfeit() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_two, LEAVE_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 is told player 2 left
+
+    let player_two_stats = own_stats(&mut player_two).await;
+
+    assert_eq!(player_two_stats["forfeits"], 1);
+    assert_eq!(player_two_stats["losses"], 1);
+
+    let player_one_stats = own_stats(&mut player_one).await;
+
+    assert_eq!(player_one_stats["wins"], 1);
+    assert_eq!(player_one_stats["forfeits"], 0);
+}
+
+#[actix_web::test]
+async fn stats_of_unknown_players_are_not_found() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    process_message(&mut player_one).await; // Player 1 connects
+
+    let unknown_id = Uuid::new_v4().to_string();
+    send_message(&mut player_one, &build_stats_message(&unknown_id)).await;
+    let msg = process_message(&mut player_one).await;
+    let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        msg,
+        serde_json::json!({ "category": "Error", "body": "Player not found." })
+    );
+
+    let response = reqwest::get(format!(
+        "{}/players/{}/stats",
+        test_app.http_address, unknown_id
+    ))
+    .await
+    .expect("Failed to execute request.");
+
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[actix_web::test]
+async fn stats_carry_over_when_reconnecting_with_a_token() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    let (player_two_id, token) = process_connected(&mut player_two).await;
+
+    send_message(&mut player_one, &build_create_message("room")).await;
+    process_message(&mut player_one).await; // Player 1 receives the room id
+    join_room(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, START_MESSAGE).await;
+    process_message(&mut player_one).await; // Player 1 recieves game start
+    process_message(&mut player_two).await; // Player 2 recieves game start
+
+    send_message(&mut player_two, LEAVE_MESSAGE).await; // Player 2 forfeits
+    player_two.close(None).await.unwrap();
+    sleep(Duration::from_millis(50)).await; // Give the server time to drop the old session
+
+    let mut player_two = test_app.connect_player().await;
+    process_connected(&mut player_two).await;
+    send_message(&mut player_two, &build_reconnect_message(&token)).await;
+    let (reconnected_id, _) = process_connected(&mut player_two).await;
+
+    assert_eq!(reconnected_id, player_two_id);
+    assert_eq!(own_stats(&mut player_two).await["forfeits"], 1);
+    assert!(logs_contain(&player_two_id)); // The session is logged, its token is not
+    assert!(!logs_contain(&token));
+}
+
+#[actix_web::test]
+async fn reconnect_tokens_in_use_or_unknown_are_rejected() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let (_, token) = process_connected(&mut player_one).await;
+
+    let mut player_two = test_app.connect_player().await;
+    let (player_two_id, _) = process_connected(&mut player_two).await;
+
+    let expected = serde_json::json!({
+        "category": "Error",
+        "body": "Unknown or already used reconnect token.",
+    });
+    for token in [token, Uuid::new_v4().to_string()] {
+        send_message(&mut player_two, &build_reconnect_message(&token)).await;
+        let msg = process_message(&mut player_two).await;
+        let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+
+        assert_eq!(msg, expected);
+    }
+
+    assert_eq!(own_stats(&mut player_two).await["playerId"], player_two_id);
+}
@@ -0,0 +1,134 @@
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::helpers::*;
+
+#[actix_web::test]
+async fn subscriber_recieves_current_matches() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut subscriber = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut subscriber).await; // Subscriber connects
+
+    send_message(&mut player_one, &build_create_message("room")).await;
+    process_message(&mut player_one).await;
+
+    send_message(&mut subscriber, SUBSCRIBE_LOBBY_MESSAGE).await;
+
+    let subscriber_response = process_message(&mut subscriber).await;
+    let subscriber_response: MatchListResponse =
+        serde_json::from_str(subscriber_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(subscriber_response.category, "MatchList");
+    assert_eq!(subscriber_response.body.matches.len(), 1);
+}
+
+#[actix_web::test]
+async fn subscriber_recieves_every_current_match_at_once() {
+    let test_app = spawn_app().await;
+
+    let mut players: Vec<WebSocketStream<MaybeTlsStream<TcpStream>>> = vec![];
+    for index in 0..51 {
+        let mut player = test_app.connect_player().await;
+        process_message(&mut player).await; // Player connects
+        send_message(
+            &mut player,
+            &build_create_message(&format!("room {}", index)),
+        )
+        .await;
+        process_message(&mut player).await;
+        players.push(player);
+    }
+
+    let mut subscriber = test_app.connect_player().await;
+    process_message(&mut subscriber).await; // Subscriber connects
+
+    send_message(&mut subscriber, SUBSCRIBE_LOBBY_MESSAGE).await;
+
+    let subscriber_response = process_message(&mut subscriber).await;
+    let subscriber_response: serde_json::Value =
+        serde_json::from_str(subscriber_response.to_text().unwrap()).unwrap();
+
+    assert_eq!(subscriber_response["body"]["total"], 51);
+    assert_eq!(
+        subscriber_response["body"]["matches"]
+            .as_array()
+            .unwrap()
+            .len(),
+        51
+    );
+    assert!(subscriber_response["body"]["nextCursor"].is_null());
+}
+
+#[actix_web::test]
+async fn subscriber_is_not_told_about_private_rooms() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut subscriber = test_app.connect_player().await;
+
+    process_message(&mut player_one).await; // Player 1 connects
+    process_message(&mut subscriber).await; // Subscriber connects
+
+    send_message(&mut subscriber, SUBSCRIBE_LOBBY_MESSAGE).await;
+    process_message(&mut subscriber).await; // Subscriber recieves match list
+
+    let options = serde_json::json!({ "name": "private room", "private": true });
+    send_message(&mut player_one, &build_create_message_with_options(options)).await;
+    process_message(&mut player_one).await;
+    send_message(&mut player_one, LEAVE_MESSAGE).await;
+
+    assert!(
+        process_message_result(&mut subscriber).await.is_none(),
+        "Private rooms are neither listed nor removed"
+    );
+}
+
+#[actix_web::test]
+async fn subscriber_is_notified_of_room_lifecycle() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut subscriber = test_app.connect_player().await;
+
+    process_message(&mut subscriber).await; // Subscriber connects
+
+    send_message(&mut subscriber, SUBSCRIBE_LOBBY_MESSAGE).await;
+    process_message(&mut subscriber).await; // Subscriber recieves match list
+
+    setup_game(&mut player_one, &mut player_two).await;
+
+    let created = process_message(&mut subscriber).await;
+    let created: serde_json::Value = serde_json::from_str(created.to_text().unwrap()).unwrap();
+
+    assert_eq!(created["category"], "RoomCreated");
+    assert_eq!(created["body"]["players"], "1/2");
+
+    let joined = process_message(&mut subscriber).await;
+    let joined: serde_json::Value = serde_json::from_str(joined.to_text().unwrap()).unwrap();
+
+    assert_eq!(joined["category"], "RoomUpdated");
+    assert_eq!(joined["body"]["players"], "2/2");
+
+    send_message(&mut player_one, START_MESSAGE).await;
+
+    let started = process_message(&mut subscriber).await;
+    let started: serde_json::Value = serde_json::from_str(started.to_text().unwrap()).unwrap();
+
+    assert_eq!(started["category"], "RoomUpdated");
+    assert_eq!(started["body"]["status"], "Started");
+
+    send_message(&mut player_one, LEAVE_MESSAGE).await;
+    process_message(&mut subscriber).await; // Subscriber recieves room update
+    send_message(&mut player_two, LEAVE_MESSAGE).await;
+
+    let removed = process_message(&mut subscriber).await;
+    let removed: serde_json::Value = serde_json::from_str(removed.to_text().unwrap()).unwrap();
+
+    assert_eq!(removed["category"], "RoomRemoved");
+    assert_eq!(removed["body"]["matchId"], created["body"]["matchId"]);
+}
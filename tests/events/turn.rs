@@ -28,7 +28,7 @@ async fn server_ignores_duplicate_turn() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
+    send_turn(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
 
     process_message(&mut player_two).await; // Player 2 recieves turn
 
@@ -51,11 +51,11 @@ async fn server_processes_valid_turn() {
 
     setup_and_start_game(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
+    send_turn(&mut player_one, &build_turn_message("MM")).await; // Player 1 turn
 
     let player_two_msg = process_message_result(&mut player_two).await; // Player 2 recieves turn
 
-    send_message(&mut player_two, &build_turn_message("LL")).await; // Player 2 turn
+    send_turn(&mut player_two, &build_turn_message("LL")).await; // Player 2 turn
 
     let player_one_msg = process_message_result(&mut player_one).await; // Player 1 recieves turn
 
@@ -99,7 +99,7 @@ async fn game_ends_on_tie() {
 
     setup_game_for_tie(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("ML")).await; // Final turn
+    send_turn(&mut player_one, &build_turn_message("ML")).await; // Final turn
     process_message(&mut player_two).await; // Player 2 recieves final turn
 
     let player_one_msg = process_message(&mut player_one).await;
@@ -128,7 +128,7 @@ async fn game_ends_on_diagonal_victory() {
 
     setup_game_for_diagonal_victory(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("UR")).await; // Final turn
+    send_turn(&mut player_one, &build_turn_message("UR")).await; // Final turn
     process_message(&mut player_two).await; // Player 2 recieves final turn
 
     let player_one_msg = process_message(&mut player_one).await;
@@ -162,7 +162,7 @@ async fn game_ends_on_diagonal_mirror_victory() {
 
     setup_game_for_diagonal_mirror_victory(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LR")).await; // Final turn
+    send_turn(&mut player_one, &build_turn_message("LR")).await; // Final turn
     process_message(&mut player_two).await; // Player 2 recieves final turn
 
     let player_one_msg = process_message(&mut player_one).await;
@@ -196,7 +196,7 @@ async fn game_ends_on_cross_victory() {
 
     setup_game_for_cross_victory(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_one, &build_turn_message("LR")).await; // Final turn
+    send_turn(&mut player_one, &build_turn_message("LR")).await; // Final turn
     process_message(&mut player_two).await; // Player 2 recieves final turn
 
     let player_one_msg = process_message(&mut player_one).await;
@@ -230,7 +230,7 @@ async fn game_ends_on_circle_victory() {
 
     setup_game_for_circle_victory(&mut player_one, &mut player_two).await;
 
-    send_message(&mut player_two, &build_turn_message("UR")).await; // Final turn
+    send_turn(&mut player_two, &build_turn_message("UR")).await; // Final turn
     process_message(&mut player_one).await; // Player 1 recieves final turn
 
     let player_one_msg = process_message(&mut player_one).await;
@@ -254,3 +254,510 @@ async fn game_ends_on_circle_victory() {
     assert_eq!(player_one_msg, player_one_expected);
     assert_eq!(player_two_msg, player_two_expected);
 }
+
+#[actix_web::test]
+async fn larger_boards_accept_coordinate_turns() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "boardSize": 4 });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    send_turn(&mut player_one, &build_coordinates_turn_message(3, 3)).await;
+
+    let player_two_msg = process_message(&mut player_two).await;
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    let expected = serde_json::json!({
+        "category": "Turn",
+        "body": { "row": 3, "column": 3 }
+    });
+
+    assert_eq!(player_two_msg, expected);
+}
+
+#[actix_web::test]
+async fn larger_boards_ignore_out_of_bounds_and_legacy_turns() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "boardSize": 4 });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    send_message(&mut player_one, &build_coordinates_turn_message(4, 0)).await;
+    send_message(&mut player_one, &build_turn_message("MM")).await;
+
+    let player_two_response = process_message_result(&mut player_two).await;
+
+    assert!(
+        player_two_response.is_none(),
+        "Invalid moves are not notified to player two"
+    );
+}
+
+#[actix_web::test]
+async fn game_ends_on_configured_win_length() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "boardSize": 5, "winLength": 4 });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    for column in 0..3 {
+        send_turn(&mut player_one, &build_coordinates_turn_message(0, column)).await;
+        process_message(&mut player_two).await;
+        send_turn(&mut player_two, &build_coordinates_turn_message(1, column)).await;
+        process_message(&mut player_one).await;
+    }
+
+    send_turn(&mut player_one, &build_coordinates_turn_message(0, 3)).await; // Winning turn
+    process_message(&mut player_two).await; // Player 2 recieves winning turn
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_two_msg = process_message(&mut player_two).await;
+
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "GameOver", "body": "victory" })
+    );
+    assert_eq!(
+        player_two_msg,
+        serde_json::json!({ "category": "GameOver", "body": "defeat" })
+    );
+}
+
+fn build_board_turn_message(board: usize, row: usize, column: usize) -> String {
+    serde_json::json!({
+        "message": "Turn",
+        "content": { "board": board, "row": row, "column": column }
+    })
+    .to_string()
+}
+
+#[actix_web::test]
+async fn ultimate_moves_decide_the_next_board() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "variant": "Ultimate" });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    let player_one_turn = send_turn(&mut player_one, &build_board_turn_message(4, 0, 2)).await;
+    assert_eq!(
+        player_one_turn["nextBoard"], 2,
+        "The mover learns the next board too"
+    );
+
+    let player_two_msg = process_message(&mut player_two).await;
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_two_msg["category"], "Turn");
+    assert_eq!(player_two_msg["body"]["board"], 4);
+    assert_eq!(player_two_msg["body"]["nextBoard"], 2);
+    assert_eq!(player_two_msg["body"]["boards"][4], "Open");
+
+    send_message(&mut player_two, &build_board_turn_message(3, 1, 1)).await; // Wrong board
+
+    let player_one_response = process_message_result(&mut player_one).await;
+    assert!(
+        player_one_response.is_none(),
+        "Moves outside of the forced board are ignored"
+    );
+
+    send_turn(&mut player_two, &build_board_turn_message(2, 1, 1)).await;
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_msg["body"]["board"], 2);
+    assert_eq!(player_one_msg["body"]["nextBoard"], 4);
+}
+
+#[actix_web::test]
+async fn completing_a_line_loses_in_misere() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "variant": "Misere" });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    for (player_one_move, player_two_move) in [("LL", "UL"), ("LM", "UM")] {
+        send_turn(&mut player_one, &build_turn_message(player_one_move)).await;
+        process_message(&mut player_two).await;
+        send_turn(&mut player_two, &build_turn_message(player_two_move)).await;
+        process_message(&mut player_one).await;
+    }
+
+    send_turn(&mut player_one, &build_turn_message("LR")).await; // Completes a line
+    process_message(&mut player_two).await; // Player 2 recieves final turn
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_two_msg = process_message(&mut player_two).await;
+
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "GameOver", "body": "defeat" })
+    );
+    assert_eq!(
+        player_two_msg,
+        serde_json::json!({ "category": "GameOver", "body": "victory" })
+    );
+}
+
+fn build_column_turn_message(column: usize) -> String {
+    serde_json::json!({ "message": "Turn", "content": { "column": column } }).to_string()
+}
+
+#[actix_web::test]
+async fn gravity_moves_fall_to_the_lowest_empty_row() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({
+        "name": "room",
+        "variant": "Gravity",
+        "boardSize": 7,
+        "boardHeight": 6,
+        "winLength": 4
+    });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    let player_one_turn = send_turn(&mut player_one, &build_column_turn_message(3)).await;
+    let player_two_msg = process_message(&mut player_two).await;
+
+    send_turn(&mut player_two, &build_column_turn_message(3)).await;
+    let player_one_msg = process_message(&mut player_one).await;
+
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_turn,
+        serde_json::json!({ "row": 5, "column": 3 }),
+        "The mover learns where the piece fell"
+    );
+    assert_eq!(
+        player_two_msg,
+        serde_json::json!({ "category": "Turn", "body": { "row": 5, "column": 3 } })
+    );
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "Turn", "body": { "row": 4, "column": 3 } })
+    );
+}
+
+fn build_wild_turn_message(row: usize, column: usize, symbol: &str) -> String {
+    serde_json::json!({
+        "message": "Turn",
+        "content": { "row": row, "column": column, "symbol": symbol }
+    })
+    .to_string()
+}
+
+#[actix_web::test]
+async fn wild_moves_place_the_chosen_symbol() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "variant": "Wild" });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    send_message(&mut player_one, &build_coordinates_turn_message(0, 0)).await; // No symbol
+    assert!(
+        process_message_result(&mut player_two).await.is_none(),
+        "Wild moves without a symbol are ignored"
+    );
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "Error", "body": "Wild moves need a symbol." })
+    );
+
+    send_turn(&mut player_one, &build_wild_turn_message(0, 0, "O")).await;
+
+    let player_two_msg = process_message(&mut player_two).await;
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_two_msg,
+        serde_json::json!({
+            "category": "Turn",
+            "body": { "row": 0, "column": 0, "symbol": "Circle" }
+        })
+    );
+
+    send_turn(&mut player_two, &build_wild_turn_message(1, 1, "X")).await;
+    process_message(&mut player_one).await;
+    send_turn(&mut player_one, &build_wild_turn_message(0, 1, "Circle")).await;
+    process_message(&mut player_two).await;
+    send_turn(&mut player_two, &build_wild_turn_message(2, 2, "X")).await;
+    process_message(&mut player_one).await;
+    send_turn(&mut player_one, &build_wild_turn_message(0, 2, "Circle")).await; // Completes a line
+    process_message(&mut player_two).await;
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "GameOver", "body": "victory" })
+    );
+}
+
+#[actix_web::test]
+async fn killing_the_last_notakto_board_loses() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "variant": "Notakto", "boardCount": 2 });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    send_turn(&mut player_one, &build_board_turn_message(0, 0, 0)).await;
+    process_message(&mut player_two).await;
+    send_turn(&mut player_two, &build_board_turn_message(0, 0, 1)).await;
+    process_message(&mut player_one).await;
+    send_turn(&mut player_one, &build_board_turn_message(0, 0, 2)).await; // Kills board 0
+
+    let player_two_msg = process_message(&mut player_two).await;
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+    assert_eq!(
+        player_two_msg["body"]["deadBoards"],
+        serde_json::json!([true, false])
+    );
+
+    send_message(&mut player_two, &build_board_turn_message(0, 1, 1)).await; // Dead board
+    assert!(
+        process_message_result(&mut player_one).await.is_none(),
+        "Moves on dead boards are ignored"
+    );
+
+    send_turn(&mut player_two, &build_board_turn_message(1, 0, 0)).await;
+    process_message(&mut player_one).await;
+    send_turn(&mut player_one, &build_board_turn_message(1, 1, 1)).await;
+    process_message(&mut player_two).await;
+    send_turn(&mut player_two, &build_board_turn_message(1, 2, 2)).await; // Kills the last board
+    process_message(&mut player_one).await;
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_two_msg = process_message(&mut player_two).await;
+
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "GameOver", "body": "victory" })
+    );
+    assert_eq!(
+        player_two_msg,
+        serde_json::json!({ "category": "GameOver", "body": "defeat" })
+    );
+}
+
+fn build_quantum_turn_message(first: (usize, usize), second: (usize, usize)) -> String {
+    serde_json::json!({
+        "message": "Turn",
+        "content": {
+            "cells": [
+                { "row": first.0, "column": first.1 },
+                { "row": second.0, "column": second.1 }
+            ]
+        }
+    })
+    .to_string()
+}
+
+#[actix_web::test]
+async fn quantum_cycles_collapse_on_the_other_players_choice() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    let options = serde_json::json!({ "name": "room", "variant": "Quantum" });
+    setup_and_start_game_with_options(&mut player_one, &mut player_two, options).await;
+
+    send_turn(&mut player_one, &build_quantum_turn_message((0, 0), (1, 1))).await;
+    process_message(&mut player_two).await;
+    send_turn(&mut player_two, &build_quantum_turn_message((1, 1), (2, 2))).await;
+    process_message(&mut player_one).await;
+    let player_one_turn =
+        send_turn(&mut player_one, &build_quantum_turn_message((2, 2), (0, 0))).await; // Closes a cycle
+    assert_eq!(
+        player_one_turn["moveNumber"], 3,
+        "The mover learns the move number"
+    );
+
+    let player_two_msg = process_message(&mut player_two).await;
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+    assert_eq!(player_two_msg["body"]["moveNumber"], 3);
+    assert_eq!(player_two_msg["body"]["collapsePending"], true);
+
+    send_message(&mut player_two, &build_quantum_turn_message((0, 1), (0, 2))).await;
+    assert!(
+        process_message_result(&mut player_one).await.is_none(),
+        "Moves are ignored until the cycle collapses"
+    );
+
+    let collapse = serde_json::json!({
+        "message": "Collapse",
+        "content": { "row": 0, "column": 0 }
+    });
+    send_message(&mut player_two, &collapse.to_string()).await;
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(player_one_msg["category"], "Collapse");
+    assert_eq!(
+        player_one_msg["body"]["board"]["classical"]["0,0"],
+        serde_json::json!({ "symbol": "Cross", "moveNumber": 3 })
+    );
+    assert_eq!(
+        player_one_msg["body"]["board"]["classical"]["1,1"],
+        serde_json::json!({ "symbol": "Cross", "moveNumber": 1 })
+    );
+    assert_eq!(
+        player_one_msg["body"]["board"]["classical"]["2,2"],
+        serde_json::json!({ "symbol": "Circle", "moveNumber": 2 })
+    );
+}
+
+#[actix_web::test]
+async fn three_player_turns_rotate_across_every_seat() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+    let mut player_three = test_app.connect_player().await;
+
+    setup_three_player_game(&mut player_one, &mut player_two, &mut player_three).await;
+
+    send_message(&mut player_one, START_MESSAGE).await;
+    process_message(&mut player_one).await;
+    process_message(&mut player_two).await;
+    process_message(&mut player_three).await;
+
+    send_turn(&mut player_one, &build_coordinates_turn_message(0, 0)).await;
+    process_message(&mut player_two).await;
+    process_message(&mut player_three).await;
+
+    send_message(&mut player_three, &build_coordinates_turn_message(1, 1)).await; // Out of turn
+    assert!(
+        process_message_result(&mut player_one).await.is_none(),
+        "Player 3 has to wait for player 2"
+    );
+
+    send_turn(&mut player_two, &build_coordinates_turn_message(1, 1)).await;
+    process_message(&mut player_one).await;
+    process_message(&mut player_three).await;
+
+    send_turn(&mut player_three, &build_coordinates_turn_message(2, 2)).await;
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "Turn", "body": { "row": 2, "column": 2 } })
+    );
+}
+
+#[actix_web::test]
+async fn unknown_moves_are_reported_to_the_mover() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    send_message(&mut player_one, &build_turn_message("zz")).await;
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "Error", "body": "Unknown move \"zz\"." })
+    );
+    assert!(
+        process_message_result(&mut player_two).await.is_none(),
+        "Unknown moves are not relayed"
+    );
+}
+
+#[actix_web::test]
+async fn moves_are_written_in_each_sessions_notation() {
+    let test_app = spawn_app().await;
+
+    let mut player_one = test_app.connect_player().await;
+    let mut player_two = test_app.connect_player().await;
+
+    send_message(&mut player_two, &build_notation_message("Numpad")).await;
+    setup_and_start_game(&mut player_one, &mut player_two).await;
+
+    send_turn(&mut player_one, &build_turn_message("MM")).await;
+
+    let player_two_msg = process_message(&mut player_two).await;
+    let player_two_msg: serde_json::Value =
+        serde_json::from_str(player_two_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_two_msg,
+        serde_json::json!({ "category": "Turn", "body": "5" })
+    );
+
+    send_turn(&mut player_two, &build_turn_message("a3")).await; // Algebraic for UL
+
+    let player_one_msg = process_message(&mut player_one).await;
+    let player_one_msg: serde_json::Value =
+        serde_json::from_str(player_one_msg.to_text().unwrap()).unwrap();
+
+    assert_eq!(
+        player_one_msg,
+        serde_json::json!({ "category": "Turn", "body": "UL" })
+    );
+}